@@ -1,14 +1,25 @@
+use mensagens::Lang; // Catálogo de mensagens em português e inglês, compartilhado com os outros programas
 use rand::Rng; // Rng é um trait
-use std::{cmp::Ordering, io}; // io é uma crate, Ordering é um tipo soma da biblioteca `cmp`
+use std::{cmp::Ordering, env, io, process}; // io é uma crate, Ordering é um tipo soma da biblioteca `cmp`
 
 fn main() {
-    println!("Adivinhe o número!");
+    // O idioma vem de `--lang=en` (ou `--lang en`) e, sem a opção, da variável LANG
+    let idioma = match Lang::select(env::args().skip(1)) {
+        Ok((idioma, _)) => idioma,
+        Err(erro) => {
+            eprintln!("{erro}");
+            process::exit(2);
+        }
+    };
+    let sair = idioma.text("adivinhacao.sair"); // A palavra que termina o jogo também é traduzida
+
+    println!("{}", idioma.text("adivinhacao.titulo"));
 
     let secret_number = rand::thread_rng() // O gerador de número
                                 .gen_range(1..=100); // gera um número número aleatório no range definido por star..=end [start, end]
 
     loop {
-        println!("Por favor, digite o seu chute. (digite 'sair' para sair)");
+        println!("{}", idioma.format("adivinhacao.convite", &[&sair]));
 
         let mut guess_str = String::new(); // :: é uma sintaxe que indica que um método está associada à um tipo ou trait
 
@@ -17,8 +28,8 @@ fn main() {
             .read_line(&mut guess_str) // O método read_line é chamado no tratador de input padrão (std::io::Stdin). Seu argumento é a string mutável `guess_str`. À ela, é anexada a entrada do usuário (por isso a string de argumento tem que se mutável)
             // `guess_str` é passado como referência, i.e, o método acessa o espaço de memória em que a variável está definida, não criamos uma cópia da variável em outro espaço de memória para aplicar o método.
             // .read_line retorna um Result - um tipo soma que pode ser ou um `Ok` ou `Err`
-            .expect("Falha ao ler a linha");
-            // O expect:
+            .unwrap_or_else(|_| panic!("{}", idioma.text("comum.falha_leitura_linha")));
+            // O unwrap_or_else (que faz o papel do expect, mas com a mensagem do catálogo):
                 // Causa um crash no programa e retorna uma mensagem de erro se a instância do Result for `Err`
                 // Retorna o valor que `Ok` está guardadndo se a instância do Result for `Ok`
 
@@ -29,17 +40,17 @@ fn main() {
                 match guess_str.trim().parse::<f32>() {
                     Ok(number) => {
                         if number < 0.0 {
-                            println!("Digite um número positivo!");
+                            println!("{}", idioma.text("adivinhacao.positivo"));
                         } else {
-                            println!("Digite um número inteiro de 1 a 100!");
+                            println!("{}", idioma.text("adivinhacao.inteiro"));
                         }
                         continue;
                     },
                     Err(_) => {
-                        if guess_str.trim() == "sair" {
+                        if guess_str.trim() == sair {
                             break;
                         } else {
-                            println!("Digite um número!");
+                            println!("{}", idioma.text("adivinhacao.numero"));
                             continue;
                         }
                     }
@@ -47,15 +58,15 @@ fn main() {
             }
         }; // Note que nem toda variável pode ser convertida para um inteiro. Por isso, o parse() retorna, assim como o read_line(), um Result. Tratamos ele de forma a terminar o programa se o usuário digitou "quit" e ignorar o input se ele digitou uma letra qualquer ou um número fora do escopo.
 
-        // Essa linha tem o mesmo resultado de `println!("You guessed: {guess}")`
-        println!("Você chutou: {}", guess); // {} é um placeholder para a expressão após a vírgula
+        // No catálogo, {0} é o lugar do primeiro argumento, como o {} do println!
+        println!("{}", idioma.format("adivinhacao.chute", &[&guess])); // {} é um placeholder para a expressão após a vírgula
 
         // O match representa o casamento de padrões do Rust. Ele associa a cada variante de Ordering um comportamento do programa
         match guess.cmp(&secret_number) { // O método cmp compara dois valores e retorna um algo do tipo Ordering (o resultado da comparação entre guess e secret_number)
-            Ordering::Less    => println!("Muito pequeno!"), // Comportamento se guess < secret_number
-            Ordering::Greater => println!("Muito grande!"), // Comportamento se guess > secret_number
+            Ordering::Less    => println!("{}", idioma.text("adivinhacao.pequeno")), // Comportamento se guess < secret_number
+            Ordering::Greater => println!("{}", idioma.text("adivinhacao.grande")), // Comportamento se guess > secret_number
             Ordering::Equal   => {
-                println!("Você ganhou!"); // Comportamento se guess == secret_number
+                println!("{}", idioma.text("adivinhacao.ganhou")); // Comportamento se guess == secret_number
                 break; // Termina o jogo
             }
         } // Note que, aqui, `::` não tem o mesmo significado das ocorrências anteriores
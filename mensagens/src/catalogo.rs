@@ -0,0 +1,275 @@
+// As mensagens de todos os programas, agrupadas pelo prefixo da chave:
+// `comum` vale para todos, `adivinhacao` é o guessing_game, `two_sat` o
+// two_sat e o resto é do multiplicacao_de_matrizes (um prefixo por módulo).
+
+pub(crate) struct Mensagem {
+    pub chave: &'static str,
+    pub pt_br: &'static str,
+    pub en: &'static str,
+}
+
+const fn m(chave: &'static str, pt_br: &'static str, en: &'static str) -> Mensagem {
+    Mensagem { chave, pt_br, en }
+}
+
+pub(crate) const CATALOGO: &[Mensagem] = &[
+    m(
+        "comum.idioma_desconhecido",
+        "Idioma desconhecido \"{0}\", use pt-BR ou en",
+        "Unknown language \"{0}\", use pt-BR or en",
+    ),
+    m("comum.falha_leitura_linha", "Falha ao ler a linha", "Failed to read the line"),
+    m("comum.falha_leitura_entrada", "Falha ao ler a entrada", "Failed to read the input"),
+    // guessing_game
+    m("adivinhacao.titulo", "Adivinhe o número!", "Guess the number!"),
+    m(
+        "adivinhacao.convite",
+        "Por favor, digite o seu chute. (digite '{0}' para sair)",
+        "Please input your guess. (type '{0}' to quit)",
+    ),
+    m("adivinhacao.sair", "sair", "quit"),
+    m("adivinhacao.positivo", "Digite um número positivo!", "Enter a positive number!"),
+    m("adivinhacao.inteiro", "Digite um número inteiro de 1 a 100!", "Enter a whole number from 1 to 100!"),
+    m("adivinhacao.numero", "Digite um número!", "Enter a number!"),
+    m("adivinhacao.chute", "Você chutou: {0}", "You guessed: {0}"),
+    m("adivinhacao.pequeno", "Muito pequeno!", "Too small!"),
+    m("adivinhacao.grande", "Muito grande!", "Too big!"),
+    m("adivinhacao.ganhou", "Você ganhou!", "You win!"),
+    // two_sat
+    m("two_sat.satisfativel", "Satisfatível! Atribuição: {0}", "Satisfiable! Assignment: {0}"),
+    m("two_sat.insatisfativel", "Insatisfatível!", "Unsatisfiable!"),
+    // multiplicacao_de_matrizes: o programa
+    m(
+        "matrizes.uso",
+        "Uso: multiplicacao_de_matrizes [--tables[=grade|csv]] [--all] [--cost-of=EXPR] [--batch]
+                              [--max-memory=N] [--pareto] [--lang=pt-BR|en]
+  --tables      mostra as tabelas m e s alinhadas (ou em CSV com --tables=csv)
+  --all         lista todas as associações com o custo mínimo
+  --cost-of     compara a associação EXPR, como ((A1A2)A3), com a ótima
+  --batch       lê várias cadeias (uma por linha ou um array JSON) e escreve
+                um resultado em JSON por linha; com --tables inclui m e s
+  --max-memory  a ordem mais barata em que nenhum produto passa de N elementos
+  --pareto      lista as ordens que trocam multiplicações por memória
+  --lang        idioma das mensagens; sem a opção vale o de LANG",
+        "Usage: multiplicacao_de_matrizes [--tables[=grade|csv]] [--all] [--cost-of=EXPR] [--batch]
+                                [--max-memory=N] [--pareto] [--lang=pt-BR|en]
+  --tables      prints the m and s tables aligned (or as CSV with --tables=csv)
+  --all         lists every parenthesization with the minimum cost
+  --cost-of     compares the parenthesization EXPR, like ((A1A2)A3), with the optimal one
+  --batch       reads several chains (one per line or a JSON array) and writes
+                one JSON result per line; with --tables includes m and s
+  --max-memory  the cheapest order in which no product exceeds N elements
+  --pareto      lists the orders that trade multiplications for memory
+  --lang        language of the messages; without it LANG decides",
+    ),
+    m("matrizes.opcao_desconhecida", "Opção desconhecida: {0}", "Unknown option: {0}"),
+    m(
+        "matrizes.opcao_incompativel_com_lote",
+        "A opção {0} não pode ser usada com --batch",
+        "The option {0} cannot be used with --batch",
+    ),
+    m(
+        "matrizes.convite_dimensoes",
+        "Digite as dimensões das matrizes (separe por vírgulas ou espaços, ou use pares como 10x20, 20x30)",
+        "Enter the matrix dimensions (separated by commas or spaces, or as pairs like 10x20, 20x30)",
+    ),
+    m(
+        "matrizes.convite_nomes",
+        "ou dê nomes a elas, com dimensões simbólicas opcionais: X: n x d, W: d x 5; n=100, d=20",
+        "or name them, with optional symbolic dimensions: X: n x d, W: d x 5; n=100, d=20",
+    ),
+    m(
+        "matrizes.custo_minimo",
+        "A menor quantidade de multiplicações possível é {0}.",
+        "The fewest possible multiplications is {0}.",
+    ),
+    m(
+        "matrizes.expressao",
+        "Ela ocorre quando multiplicamos as matrizes de acordo com a expressão a seguir:",
+        "It is reached by multiplying the matrices according to the following expression:",
+    ),
+    m(
+        "matrizes.custo_da_associacao",
+        "A associação {0} custa {1} multiplicações, {2} a mais que a ótima.",
+        "The parenthesization {0} costs {1} multiplications, {2} more than the optimal one.",
+    ),
+    m(
+        "matrizes.teto_invalido",
+        "Valor inválido para --max-memory: {0}, use um inteiro não negativo",
+        "Invalid value for --max-memory: {0}, use a non-negative integer",
+    ),
+    m(
+        "matrizes.custo_com_teto",
+        "Com no máximo {0} elementos por produto, a menor quantidade de multiplicações é {1}.",
+        "With at most {0} elements per product, the fewest multiplications is {1}.",
+    ),
+    m(
+        "matrizes.pico",
+        "O maior produto calculado tem {0} elementos.",
+        "The largest product computed has {0} elements.",
+    ),
+    m(
+        "matrizes.fronteira",
+        "Ordens que trocam multiplicações por memória:",
+        "Orders that trade multiplications for memory:",
+    ),
+    m(
+        "matrizes.ponto_da_fronteira",
+        "{0} multiplicações, pico de {1} elementos: {2}",
+        "{0} multiplications, peak of {1} elements: {2}",
+    ),
+    m(
+        "matrizes.unica_associacao",
+        "Essa é a única associação com esse custo.",
+        "This is the only parenthesization with that cost.",
+    ),
+    m(
+        "matrizes.todas_associacoes",
+        "Todas as {0} associações com esse custo:",
+        "All {0} parenthesizations with that cost:",
+    ),
+    // multiplicacao_de_matrizes: leitura das dimensões (input)
+    m(
+        "entrada.erro_no_token",
+        "Erro no token {0} (coluna {1}, \"{2}\"): {3}",
+        "Error in token {0} (column {1}, \"{2}\"): {3}",
+    ),
+    m("entrada.numero_invalido", "valor inválido, digite números inteiros", "invalid value, enter whole numbers"),
+    m("entrada.dimensao_zero", "as dimensões devem ser maiores que zero", "dimensions must be greater than zero"),
+    m(
+        "entrada.notacao_mista",
+        "não misture pares AxB com dimensões soltas",
+        "do not mix AxB pairs with single dimensions",
+    ),
+    m(
+        "entrada.dimensoes_incompativeis",
+        "a matriz deveria ter {0} linhas para ser multiplicada pela anterior, mas tem {1}",
+        "the matrix should have {0} rows to be multiplied by the previous one, but it has {1}",
+    ),
+    m(
+        "entrada.poucas_dimensoes",
+        "Digite pelo menos duas dimensões (uma matriz)",
+        "Enter at least two dimensions (one matrix)",
+    ),
+    // multiplicacao_de_matrizes: cadeias nomeadas (named)
+    m("nomeada.erro_no_item", "Erro no item {0}: {1}", "Error in item {0}: {1}"),
+    m(
+        "nomeada.vazia",
+        "Digite pelo menos uma matriz, como X: 10x20",
+        "Enter at least one matrix, like X: 10x20",
+    ),
+    m(
+        "nomeada.sem_nome",
+        "escreva o nome da matriz antes das dimensões, como X: 10x20",
+        "write the matrix name before its dimensions, like X: 10x20",
+    ),
+    m("nomeada.nome_repetido", "a matriz {0} aparece mais de uma vez", "the matrix {0} appears more than once"),
+    m(
+        "nomeada.dimensoes_invalidas",
+        "\"{0}\" não está no formato linhas x colunas",
+        "\"{0}\" is not in the rows x columns format",
+    ),
+    m(
+        "nomeada.valor_invalido",
+        "\"{0}\" deveria ser símbolo=valor, com valor inteiro positivo",
+        "\"{0}\" should be symbol=value, with a positive whole value",
+    ),
+    m("nomeada.simbolo_sem_valor", "o símbolo {0} não recebeu valor", "the symbol {0} has no value"),
+    m(
+        "nomeada.dimensoes_incompativeis",
+        "{0} tem {1} colunas, mas {2} tem {3} linhas",
+        "{0} has {1} columns, but {2} has {3} rows",
+    ),
+    // multiplicacao_de_matrizes: associações escritas à mão (expr)
+    m("expressao.erro_na_coluna", "Erro na coluna {0}: {1}", "Error at column {0}: {1}"),
+    m("expressao.caractere_inesperado", "caractere inesperado '{0}'", "unexpected character '{0}'"),
+    m("expressao.fim_inesperado", "a expressão terminou antes da hora", "the expression ended too early"),
+    m(
+        "expressao.indice_invalido",
+        "depois de A deve vir o número da matriz",
+        "A must be followed by the matrix number",
+    ),
+    m("expressao.fora_de_ordem", "esperava A{0}, mas encontrei A{1}", "expected A{0}, but found A{1}"),
+    m(
+        "expressao.tamanho_errado",
+        "a cadeia tem {0} matrizes, mas a expressão usa {1}",
+        "the chain has {0} matrices, but the expression uses {1}",
+    ),
+    // multiplicacao_de_matrizes: plano e custo
+    m(
+        "plano.poucas_dimensoes",
+        "A cadeia precisa de pelo menos duas dimensões (uma matriz)",
+        "The chain needs at least two dimensions (one matrix)",
+    ),
+    m(
+        "plano.frequencias_incompativeis",
+        "{0} chaves precisam de {1} frequências de buscas sem sucesso, mas foram dadas {2}",
+        "{0} keys need {1} unsuccessful-search frequencies, but {2} were given",
+    ),
+    m(
+        "plano.teto_de_memoria",
+        "Nenhuma ordem cabe em {0} elementos de memória; a mais econômica precisa de {1}",
+        "No order fits in {0} elements of memory; the leanest one needs {1}",
+    ),
+    m(
+        "custo.estouro",
+        "O custo mínimo da cadeia não cabe no tipo {0}",
+        "The minimum cost of the chain does not fit in the type {0}",
+    ),
+    // multiplicacao_de_matrizes: execução da cadeia (matrix)
+    m("execucao.cadeia_vazia", "A cadeia não tem nenhuma matriz", "The chain has no matrices"),
+    m(
+        "execucao.tamanho_da_tabela",
+        "A tabela de divisões é para {0} matrizes, mas foram dadas {1}",
+        "The split table is for {0} matrices, but {1} were given",
+    ),
+    m(
+        "execucao.dimensoes_incompativeis",
+        "A{0} tem {1} colunas, mas A{2} tem {3} linhas",
+        "A{0} has {1} columns, but A{2} has {3} rows",
+    ),
+    // multiplicacao_de_matrizes: redes de tensores (contraction)
+    m(
+        "contracao.vazia",
+        "A especificação precisa de pelo menos um tensor",
+        "The specification needs at least one tensor",
+    ),
+    m(
+        "contracao.indice_invalido",
+        "'{0}' não é um índice válido, use letras",
+        "'{0}' is not a valid index, use letters",
+    ),
+    m(
+        "contracao.indice_repetido",
+        "o índice {0} aparece duas vezes no tensor {1}",
+        "the index {0} appears twice in tensor {1}",
+    ),
+    m(
+        "contracao.indice_de_saida_desconhecido",
+        "o índice {0} da saída não aparece em nenhum tensor",
+        "the output index {0} does not appear in any tensor",
+    ),
+    m("contracao.sem_tamanho", "falta o tamanho do índice {0}", "the size of index {0} is missing"),
+    m(
+        "contracao.indices_demais",
+        "a rede tem mais de 64 índices distintos",
+        "the network has more than 64 distinct indices",
+    ),
+    m(
+        "contracao.tensores_demais",
+        "{0} tensores são demais para a busca exaustiva (máximo {1})",
+        "{0} tensors are too many for the exhaustive search (at most {1})",
+    ),
+    // multiplicacao_de_matrizes: modo --batch
+    m("lote.json_invalido", "JSON inválido: {0}", "Invalid JSON: {0}"),
+    m(
+        "lote.dimensao_invalida",
+        "dimensão inválida {0}, use inteiros positivos",
+        "invalid dimension {0}, use positive integers",
+    ),
+    m(
+        "lote.cadeia_invalida",
+        "cada cadeia deve ser um array de dimensões ou uma string",
+        "each chain must be an array of dimensions or a string",
+    ),
+];
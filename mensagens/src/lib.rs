@@ -0,0 +1,199 @@
+//! Catálogo de mensagens compartilhado pelos programas do repositório, em
+//! português (pt-BR) e inglês (en).
+//!
+//! O idioma vem da opção `--lang` (`--lang=en` ou `--lang en`) e, sem ela,
+//! das variáveis `LC_ALL`, `LC_MESSAGES` e `LANG`, nessa ordem. Locales que
+//! não são português nem inglês, como `C`, ficam com o padrão pt-BR.
+//!
+//! As mensagens são identificadas por chaves como `"adivinhacao.titulo"` e
+//! podem ter parâmetros posicionais `{0}`, `{1}`… preenchidos por
+//! [`Lang::format`].
+
+mod catalogo;
+
+use std::{env, fmt};
+
+use catalogo::CATALOGO;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    PtBr,
+    En,
+}
+
+/// Valor de `--lang` que não corresponde a nenhum idioma do catálogo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLang(pub String);
+
+impl fmt::Display for UnknownLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // O idioma pedido é justamente o que não deu para entender
+        write!(f, "{}", Lang::from_env().format("comum.idioma_desconhecido", &[&self.0]))
+    }
+}
+
+impl std::error::Error for UnknownLang {}
+
+impl Lang {
+    /// Entende tanto etiquetas (`pt-BR`, `en`) quanto locales (`en_US.UTF-8`).
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.split(['.', '@']).next().unwrap_or("").to_ascii_lowercase();
+        match tag.split(['_', '-']).next() {
+            Some("pt") => Some(Lang::PtBr),
+            Some("en") => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// Idioma do ambiente. Como no POSIX, a primeira variável definida e não
+    /// vazia decide, mesmo que o locale dela não esteja no catálogo.
+    pub fn from_env() -> Self {
+        Self::from_vars(|nome| env::var(nome).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|nome| var(nome))
+            .find(|valor| !valor.is_empty())
+            .and_then(|valor| Lang::parse(&valor))
+            .unwrap_or_default()
+    }
+
+    /// Tira `--lang` dos argumentos (sem o nome do programa) e escolhe o
+    /// idioma: o da opção, se houver, senão o do ambiente.
+    pub fn select(args: impl IntoIterator<Item = String>) -> Result<(Self, Vec<String>), UnknownLang> {
+        Self::select_with(args, Self::from_env)
+    }
+
+    fn select_with(
+        args: impl IntoIterator<Item = String>,
+        ambiente: impl FnOnce() -> Self,
+    ) -> Result<(Self, Vec<String>), UnknownLang> {
+        let mut escolhido = None;
+        let mut resto = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(argumento) = args.next() {
+            let valor = if argumento == "--lang" {
+                args.next().unwrap_or_default()
+            } else if let Some(valor) = argumento.strip_prefix("--lang=") {
+                valor.to_owned()
+            } else {
+                resto.push(argumento);
+                continue;
+            };
+            escolhido = Some(Lang::parse(&valor).ok_or(UnknownLang(valor))?);
+        }
+        Ok((escolhido.unwrap_or_else(ambiente), resto))
+    }
+
+    /// Texto da mensagem `key`, ainda com os parâmetros `{0}`, `{1}`….
+    pub fn text(self, key: &str) -> &'static str {
+        let mensagem = CATALOGO
+            .iter()
+            .find(|m| m.chave == key)
+            .unwrap_or_else(|| panic!("a mensagem {key} não está no catálogo"));
+        match self {
+            Lang::PtBr => mensagem.pt_br,
+            Lang::En => mensagem.en,
+        }
+    }
+
+    /// Mensagem `key` com `{i}` trocado por `args[i]`.
+    pub fn format(self, key: &str, args: &[&dyn fmt::Display]) -> String {
+        let mut texto = String::new();
+        let mut resto = self.text(key);
+        while let Some(inicio) = resto.find('{') {
+            texto.push_str(&resto[..inicio]);
+            let depois = &resto[inicio + 1..];
+            let fim = depois.find('}').expect("parâmetro sem '}' no catálogo");
+            let indice: usize = depois[..fim].parse().expect("parâmetro não numérico no catálogo");
+            texto.push_str(&args[indice].to_string());
+            resto = &depois[fim + 1..];
+        }
+        texto.push_str(resto);
+        texto
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn argumentos(texto: &str) -> Vec<String> {
+        texto.split_whitespace().map(str::to_owned).collect()
+    }
+
+    // Os parâmetros {i} que aparecem numa mensagem, ordenados
+    fn parametros(texto: &str) -> Vec<&str> {
+        let mut encontrados: Vec<&str> = texto
+            .split('{')
+            .skip(1)
+            .map(|pedaco| &pedaco[..pedaco.find('}').unwrap()])
+            .collect();
+        encontrados.sort();
+        encontrados.dedup();
+        encontrados
+    }
+
+    #[test]
+    fn test_catalog_is_complete() {
+        for (posicao, mensagem) in CATALOGO.iter().enumerate() {
+            assert!(
+                CATALOGO[..posicao].iter().all(|m| m.chave != mensagem.chave),
+                "chave repetida {}",
+                mensagem.chave
+            );
+            assert!(!mensagem.pt_br.is_empty() && !mensagem.en.is_empty(), "{} sem tradução", mensagem.chave);
+            assert_eq!(parametros(mensagem.pt_br), parametros(mensagem.en), "parâmetros de {}", mensagem.chave);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Lang::parse("pt-BR"), Some(Lang::PtBr));
+        assert_eq!(Lang::parse("pt_BR.UTF-8"), Some(Lang::PtBr));
+        assert_eq!(Lang::parse("en"), Some(Lang::En));
+        assert_eq!(Lang::parse("en_US.UTF-8"), Some(Lang::En));
+        assert_eq!(Lang::parse("EN_gb@euro"), Some(Lang::En));
+        assert_eq!(Lang::parse("C"), None);
+        assert_eq!(Lang::parse("fr_FR"), None);
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let ambiente = |pares: &[(&str, &str)]| {
+            let variaveis: HashMap<String, String> =
+                pares.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            Lang::from_vars(|nome| variaveis.get(nome).cloned())
+        };
+        assert_eq!(ambiente(&[]), Lang::PtBr);
+        assert_eq!(ambiente(&[("LANG", "en_US.UTF-8")]), Lang::En);
+        assert_eq!(ambiente(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "pt_BR.UTF-8")]), Lang::PtBr);
+        // LC_ALL vazio não conta, mas um locale desconhecido decide
+        assert_eq!(ambiente(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "")]), Lang::En);
+        assert_eq!(ambiente(&[("LANG", "en_US.UTF-8"), ("LC_MESSAGES", "C")]), Lang::PtBr);
+    }
+
+    #[test]
+    fn test_select() {
+        let seleciona = |texto: &str| Lang::select_with(argumentos(texto), || Lang::PtBr);
+        assert_eq!(seleciona("--all"), Ok((Lang::PtBr, argumentos("--all"))));
+        assert_eq!(seleciona("--lang=en --all"), Ok((Lang::En, argumentos("--all"))));
+        assert_eq!(seleciona("--all --lang en --batch"), Ok((Lang::En, argumentos("--all --batch"))));
+        assert_eq!(seleciona("--lang fr"), Err(UnknownLang("fr".to_owned())));
+        assert_eq!(seleciona("--lang"), Err(UnknownLang(String::new())));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Lang::En.format("adivinhacao.chute", &[&42]), "You guessed: 42");
+        assert_eq!(Lang::PtBr.format("adivinhacao.chute", &[&42]), "Você chutou: 42");
+        assert_eq!(
+            Lang::En.format("matrizes.custo_da_associacao", &[&"(A1A2)", &10, &3]),
+            "The parenthesization (A1A2) costs 10 multiplications, 3 more than the optimal one."
+        );
+    }
+}
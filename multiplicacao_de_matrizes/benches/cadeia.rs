@@ -0,0 +1,62 @@
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use multiplicacao_de_matrizes::{Strategy, multiplicacao_de_matrizes, optimal_order};
+
+// Dimensões pseudoaleatórias entre 1 e 100, geradas de forma determinística
+fn dimensoes(n: usize) -> Vec<usize> {
+    common::dimensoes(&mut 0x9E37_79B9_7F4A_7C15, n, 100)
+}
+
+fn cadeias_longas(c: &mut Criterion) {
+    let mut grupo = c.benchmark_group("multiplicacao_de_matrizes");
+    grupo.sample_size(10);
+    for n in [250, 500, 1000, 1500] {
+        let p = dimensoes(n);
+        grupo.bench_with_input(BenchmarkId::from_parameter(n), &p, |b, p| {
+            b.iter(|| multiplicacao_de_matrizes::<u64>(black_box(p)))
+        });
+    }
+    grupo.finish();
+}
+
+// Compara as duas estratégias para achar o ponto a partir do qual Hu–Shing
+// passa a compensar (entre 12 e 16 matrizes na máquina de desenvolvimento)
+// e vai até cadeias em que o O(n³) já perde por três ordens de grandeza
+fn estrategias(c: &mut Criterion) {
+    let mut grupo = c.benchmark_group("estrategias");
+    grupo.sample_size(10);
+    for n in [4, 8, 12, 16, 32, 64, 128, 256, 512, 1024] {
+        let p = dimensoes(n);
+        for (nome, estrategia) in [("dinamica", Strategy::Dynamic), ("hu_shing", Strategy::HuShing)] {
+            grupo.bench_with_input(BenchmarkId::new(nome, n), &p, |b, p| {
+                b.iter(|| optimal_order::<u64>(black_box(p), estrategia))
+            });
+        }
+    }
+    grupo.finish();
+}
+
+// Versão sequencial contra a frente de onda paralela numa cadeia longa
+#[cfg(feature = "parallel")]
+fn frente_de_onda(c: &mut Criterion) {
+    use multiplicacao_de_matrizes::parallel::multiplicacao_de_matrizes_paralela;
+
+    let mut grupo = c.benchmark_group("frente_de_onda");
+    grupo.sample_size(10);
+    let p = dimensoes(2000);
+    grupo.bench_with_input("sequencial/2000", &p, |b, p| {
+        b.iter(|| multiplicacao_de_matrizes::<u64>(black_box(p)))
+    });
+    grupo.bench_with_input("paralela/2000", &p, |b, p| {
+        b.iter(|| multiplicacao_de_matrizes_paralela::<u64>(black_box(p)))
+    });
+    grupo.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
+fn frente_de_onda(_: &mut Criterion) {}
+
+criterion_group!(benches, cadeias_longas, estrategias, frente_de_onda);
+criterion_main!(benches);
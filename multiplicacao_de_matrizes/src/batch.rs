@@ -0,0 +1,165 @@
+//! Modo não interativo: várias cadeias de uma vez, com um resultado em JSON
+//! por linha, para uso em scripts.
+//!
+//! A entrada é uma cadeia por linha, no mesmo formato da entrada interativa,
+//! ou um array JSON em que cada elemento é um array de dimensões
+//! (`[30, 35, 15]`) ou uma string nesse mesmo formato (`"10x20, 20x5"`).
+
+use mensagens::Lang;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Notation, ParenTree, TriangularTable, multiplicacao_de_matrizes, parse_dimensions};
+
+/// Uma cadeia lida da entrada, com a posição dela (linha ou elemento do
+/// array, a partir de 1) para identificar o resultado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchItem {
+    pub index: usize,
+    pub dimensions: Result<Vec<usize>, String>,
+}
+
+/// Separa a entrada em cadeias. Linhas em branco são ignoradas; um JSON
+/// malformado vira um único item com o erro, escrito no idioma `lang`.
+pub fn read_chains(input: &str, lang: Lang) -> Vec<BatchItem> {
+    if input.trim_start().starts_with('[') {
+        return match serde_json::from_str::<Vec<Value>>(input) {
+            Ok(cadeias) => (1..).zip(cadeias).map(|(index, valor)| BatchItem { index, dimensions: dimensoes_json(&valor, lang) }).collect(),
+            Err(erro) => vec![BatchItem { index: 1, dimensions: Err(lang.format("lote.json_invalido", &[&erro])) }],
+        };
+    }
+    (1..)
+        .zip(input.lines())
+        .filter(|(_, linha)| !linha.trim().is_empty())
+        .map(|(index, linha)| BatchItem { index, dimensions: parse_dimensions(linha).map_err(|erro| erro.message(lang)) })
+        .collect()
+}
+
+fn dimensoes_json(valor: &Value, lang: Lang) -> Result<Vec<usize>, String> {
+    let p = match valor {
+        Value::String(linha) => return parse_dimensions(linha).map_err(|erro| erro.message(lang)),
+        Value::Array(dimensoes) => dimensoes
+            .iter()
+            .map(|d| {
+                d.as_u64()
+                    .and_then(|d| usize::try_from(d).ok())
+                    .filter(|&d| d > 0)
+                    .ok_or_else(|| lang.format("lote.dimensao_invalida", &[d]))
+            })
+            .collect::<Result<Vec<usize>, String>>()?,
+        _ => return Err(lang.format("lote.cadeia_invalida", &[])),
+    };
+    if p.len() < 2 {
+        return Err(lang.format("entrada.poucas_dimensoes", &[]));
+    }
+    Ok(p)
+}
+
+#[derive(Serialize)]
+struct Resultado<'a> {
+    index: usize,
+    dimensions: &'a [usize],
+    cost: u128,
+    parenthesization: String,
+    expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    m: Option<Vec<Vec<Option<u128>>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<Vec<Vec<Option<usize>>>>,
+}
+
+#[derive(Serialize)]
+struct Falha<'a> {
+    index: usize,
+    error: &'a str,
+}
+
+// Tabela n×n completa, com null abaixo da diagonal e onde não há valor
+fn linhas<T: Copy>(tabela: &TriangularTable<T>) -> Vec<Vec<Option<T>>> {
+    let n = tabela.n();
+    (1..=n)
+        .map(|i| (1..=n).map(|j| if j < i { None } else { tabela.get(i, j) }).collect())
+        .collect()
+}
+
+/// Resultado de uma cadeia como uma linha de JSON: o custo mínimo, a
+/// associação nas notações totalmente parentizada e Unicode e, se pedido,
+/// as tabelas m e s. Erros saem no idioma `lang`.
+pub fn result_json(item: &BatchItem, tables: bool, lang: Lang) -> String {
+    let falha = |erro: &str| serde_json::to_string(&Falha { index: item.index, error: erro }).unwrap();
+    let p = match &item.dimensions {
+        Ok(p) => p,
+        Err(erro) => return falha(erro),
+    };
+    let (m, s) = match multiplicacao_de_matrizes::<u128>(p) {
+        Ok(tabelas) => tabelas,
+        Err(erro) => return falha(&erro.message(lang)),
+    };
+    let n = p.len() - 1;
+    let arvore = ParenTree::from_splits(&s, 1, n);
+    let resultado = Resultado {
+        index: item.index,
+        dimensions: p,
+        cost: m.get(1, n).unwrap(),
+        parenthesization: arvore.render(p, Notation::FullyParenthesized),
+        expression: arvore.render(p, Notation::Unicode),
+        m: tables.then(|| linhas(&m)),
+        s: tables.then(|| linhas(&s)),
+    };
+    serde_json::to_string(&resultado).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lines() {
+        let itens = read_chains("30 35 15\n\n10x20, 20x5\n1 x\n", Lang::PtBr);
+        assert_eq!(itens.len(), 3);
+        assert_eq!(itens[0], BatchItem { index: 1, dimensions: Ok(vec![30, 35, 15]) });
+        assert_eq!(itens[1], BatchItem { index: 3, dimensions: Ok(vec![10, 20, 5]) });
+        assert_eq!(itens[2].index, 4);
+        assert!(itens[2].dimensions.is_err());
+    }
+
+    #[test]
+    fn test_read_json() {
+        let itens = read_chains(r#"[[30, 35, 15], "10x20, 20x5", [0, 3], [4], {"p": 1}]"#, Lang::PtBr);
+        assert_eq!(itens.len(), 5);
+        assert_eq!(itens[0].dimensions, Ok(vec![30, 35, 15]));
+        assert_eq!(itens[1].dimensions, Ok(vec![10, 20, 5]));
+        assert!(itens.iter().skip(2).all(|item| item.dimensions.is_err()));
+
+        let itens = read_chains("[[1, 2]", Lang::PtBr);
+        assert_eq!(itens.len(), 1);
+        assert!(itens[0].dimensions.as_ref().unwrap_err().starts_with("JSON inválido"));
+        let itens = read_chains("[[0, 3]]", Lang::En);
+        assert_eq!(itens[0].dimensions, Err("invalid dimension 0, use positive integers".to_owned()));
+    }
+
+    #[test]
+    fn test_result_json() {
+        let item = BatchItem { index: 2, dimensions: Ok(vec![10, 30, 5, 60]) };
+        assert_eq!(
+            result_json(&item, false, Lang::PtBr),
+            r#"{"index":2,"dimensions":[10,30,5,60],"cost":4500,"parenthesization":"((A1A2)A3)","expression":"(A₁₀ₓ₃₀∙A₃₀ₓ₅)∙A₅ₓ₆₀"}"#
+        );
+        let item = BatchItem { index: 1, dimensions: Ok(vec![1, 2, 3]) };
+        assert_eq!(
+            result_json(&item, true, Lang::PtBr),
+            r#"{"index":1,"dimensions":[1,2,3],"cost":6,"parenthesization":"(A1A2)","expression":"A₁ₓ₂∙A₂ₓ₃","m":[[0,6],[null,0]],"s":[[null,1],[null,null]]}"#
+        );
+    }
+
+    #[test]
+    fn test_errors_and_large_costs() {
+        let item = BatchItem { index: 7, dimensions: Err("ruim".to_owned()) };
+        assert_eq!(result_json(&item, false, Lang::PtBr), r#"{"index":7,"error":"ruim"}"#);
+
+        // Custos acima de u64 continuam saindo como números
+        let b = 1 << 30;
+        let item = BatchItem { index: 1, dimensions: Ok(vec![b, b, b]) };
+        assert!(result_json(&item, false, Lang::PtBr).contains(r#""cost":1237940039285380274899124224,"#));
+    }
+}
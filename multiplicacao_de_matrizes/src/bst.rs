@@ -0,0 +1,140 @@
+//! Árvore de busca binária ótima (CLRS, seção 15.5) como instância de
+//! [`interval`](crate::interval).
+//!
+//! As chaves k₁…kₙ são procuradas com frequências `p` e as buscas que caem
+//! entre duas chaves (os "fictícios" d₀…dₙ) com frequências `q`. Uma
+//! subárvore com as chaves kᵢ…kⱼ₋₁ cobre exatamente os fictícios dᵢ₋₁…dⱼ₋₁,
+//! então o problema vira um intervalo sobre os n + 1 fictícios: dividir os
+//! fictícios i..=j em i..=k e k+1..=j é pôr kₖ na raiz, e isso custa o peso
+//! total do intervalo, porque todas as buscas nele descem um nível a mais.
+
+use crate::interval::{self, SplitSearch};
+use crate::{Cost, IntervalProblem, PlanError, TriangularTable};
+
+/// O problema da árvore ótima com frequências de busca `p` (chaves) e `q`
+/// (fictícios), com `q.len() == p.len() + 1`.
+#[derive(Debug, Clone, Copy)]
+pub struct OptimalBst<'a> {
+    pub p: &'a [usize],
+    pub q: &'a [usize],
+}
+
+impl<C: Cost> IntervalProblem<C> for OptimalBst<'_> {
+    // Peso total do intervalo, vazio se não cabe em C
+    type Info = Option<C>;
+
+    fn size(&self) -> usize {
+        self.q.len()
+    }
+
+    fn info(&self, i: usize) -> Option<C> {
+        C::from_dimension(self.q[i - 1])
+    }
+
+    fn base(&self, i: usize) -> Option<C> { // Uma busca malsucedida custa uma comparação
+        C::from_dimension(self.q[i - 1])
+    }
+
+    fn combine(&self, _: usize, j: usize, left: Option<C>, right: Option<C>) -> Option<C> {
+        // Acrescentar o fictício dⱼ₋₁ traz junto a chave kⱼ₋₁
+        left?.checked_add(C::from_dimension(self.p[j - 2])?)?.checked_add(right?)
+    }
+
+    fn split(&self, _: usize, k: usize, _: usize, left: Option<C>, right: Option<C>) -> Option<C> {
+        left?.checked_add(C::from_dimension(self.p[k - 1])?)?.checked_add(right?)
+    }
+}
+
+/// Custo esperado (em comparações, ponderado pelas frequências) da árvore de
+/// busca ótima e a tabela `root`, com `root[i][j]` a chave na raiz da
+/// subárvore ótima de kᵢ…kⱼ, como em OPTIMAL-BST do CLRS.
+pub fn optimal_bst<C: Cost>(p: &[usize], q: &[usize]) -> Result<(C, TriangularTable<usize>), PlanError> {
+    optimal_bst_with(p, q, SplitSearch::Full)
+}
+
+/// Mesmo que [`optimal_bst`], escolhendo como procurar a raiz. O peso de um
+/// intervalo é uma soma de frequências, que sempre satisfaz as condições de
+/// Knuth, então [`SplitSearch::Knuth`] dá o mesmo resultado em O(n²).
+pub fn optimal_bst_with<C: Cost>(p: &[usize], q: &[usize], busca: SplitSearch) -> Result<(C, TriangularTable<usize>), PlanError> {
+    // Deve haver um fictício a mais do que chaves
+    if q.len() != p.len() + 1 {
+        return Err(PlanError::MismatchedFrequencies { keys: p.len(), dummies: q.len() });
+    }
+    let n = p.len();
+    let (m, s) = interval::solve_with::<C, _>(&OptimalBst { p, q }, busca)?;
+
+    let mut root = TriangularTable::new(n);
+    for i in 1..=n {
+        for j in i..=n {
+            if let Some(k) = s.get(i, j + 1) {
+                root.set(i, j, k);
+            }
+        }
+    }
+    Ok((m.get(1, n + 1).unwrap(), root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::proximo;
+
+    // Menor custo de uma árvore com as chaves i..=j, testando todas as raízes
+    fn forca_bruta(p: &[usize], q: &[usize], i: usize, j: usize) -> usize {
+        if j < i {
+            return q[i - 1];
+        }
+        let peso: usize = p[i - 1..j].iter().sum::<usize>() + q[i - 1..=j].iter().sum::<usize>();
+        (i..=j)
+            .map(|r| forca_bruta(p, q, i, r - 1) + forca_bruta(p, q, r + 1, j))
+            .min()
+            .unwrap()
+            + peso
+    }
+
+    #[test]
+    fn test_clrs_example() {
+        // Figura 15.9 do CLRS com as probabilidades multiplicadas por 100
+        let p = [15, 10, 5, 10, 20];
+        let q = [5, 10, 5, 5, 5, 10];
+        let (custo, root) = optimal_bst::<u64>(&p, &q).unwrap();
+        assert_eq!(custo, 275);
+        assert_eq!(root.get(1, 5), Some(2));
+        assert_eq!(root.get(3, 5), Some(5));
+        assert_eq!(root.get(3, 4), Some(4));
+    }
+
+    #[test]
+    fn test_mismatched_frequencies() {
+        assert_eq!(optimal_bst::<u64>(&[], &[]), Err(PlanError::MismatchedFrequencies { keys: 0, dummies: 0 }));
+        assert_eq!(optimal_bst::<u64>(&[3, 4], &[1, 2]), Err(PlanError::MismatchedFrequencies { keys: 2, dummies: 2 }));
+    }
+
+    #[test]
+    fn test_knuth_speed_up() {
+        let mut estado: u64 = 0x1234_5678_9ABC_DEF1;
+        let mut sorteia = || (proximo(&mut estado) % 50) as usize;
+        for n in [0, 1, 2, 5, 30, 100] {
+            let p: Vec<usize> = (0..n).map(|_| sorteia()).collect();
+            let q: Vec<usize> = (0..=n).map(|_| sorteia()).collect();
+            assert_eq!(interval::check_knuth_conditions::<u64, _>(&OptimalBst { p: &p, q: &q }), Ok(()));
+            assert_eq!(
+                optimal_bst_with::<u64>(&p, &q, SplitSearch::Knuth),
+                optimal_bst::<u64>(&p, &q),
+                "p = {p:?}, q = {q:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut estado: u64 = 0xDEAD_BEEF_CAFE_F00D;
+        let mut sorteia = || (proximo(&mut estado) % 20) as usize;
+        for n in 0..=8 {
+            let p: Vec<usize> = (0..n).map(|_| sorteia()).collect();
+            let q: Vec<usize> = (0..=n).map(|_| sorteia()).collect();
+            let (custo, _) = optimal_bst::<u64>(&p, &q).unwrap();
+            assert_eq!(custo as usize, forca_bruta(&p, &q, 1, n), "p = {p:?}, q = {q:?}");
+        }
+    }
+}
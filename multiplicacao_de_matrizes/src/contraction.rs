@@ -0,0 +1,458 @@
+//! Ordem de contração de redes de tensores no estilo do `einsum`.
+//!
+//! Cada tensor é descrito pelos seus índices (`ij` é uma matriz, `j` um
+//! vetor, `bij` um lote de matrizes) e a saída pelos índices que sobram,
+//! como em `bij,bjk->bik`. Contrair dois tensores custa o produto dos
+//! tamanhos de todos os índices envolvidos, que para duas matrizes `ij` e
+//! `jk` é o p[i-1]·p[k]·p[j] de sempre; por isso uma cadeia `ij,jk,kl,…` dá
+//! exatamente o resultado de [`multiplicacao_de_matrizes`](crate::multiplicacao_de_matrizes).
+//!
+//! Depois de contrair um grupo de tensores, um índice só continua no
+//! resultado se ainda aparece fora do grupo ou na saída; os outros já foram
+//! somados.
+
+use std::{collections::HashMap, fmt};
+
+use mensagens::Lang;
+
+use crate::{Cost, CostOverflow};
+
+/// Acima disso a busca exaustiva, O(3ⁿ), dá lugar à gulosa em [`PathStrategy::Auto`].
+pub const OPTIMAL_LIMIT: usize = 12;
+
+// A busca exaustiva indexa subconjuntos de tensores por bits de um u32
+const MAXIMO_EXAUSTIVO: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStrategy {
+    /// Programação dinâmica sobre todos os subconjuntos de tensores.
+    Optimal,
+    /// Contrai sempre o par mais barato que compartilha algum índice.
+    Greedy,
+    /// `Optimal` até [`OPTIMAL_LIMIT`] tensores, `Greedy` acima disso.
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractionError {
+    Empty,
+    // Caractere que não é letra na especificação
+    InvalidIndex(char),
+    // O mesmo índice duas vezes num tensor (traços não são suportados)
+    RepeatedIndex { tensor: usize, index: char },
+    // Índice da saída que não aparece em nenhum tensor
+    UnknownOutputIndex(char),
+    MissingSize(char),
+    // Mais índices distintos do que cabem na máscara de bits
+    TooManyIndices,
+    // Tensores demais para a busca exaustiva
+    TooManyTensors(usize),
+    Overflow(CostOverflow),
+}
+
+impl ContractionError {
+    pub fn message(&self, lang: Lang) -> String {
+        match self {
+            ContractionError::Empty => lang.format("contracao.vazia", &[]),
+            ContractionError::InvalidIndex(c) => lang.format("contracao.indice_invalido", &[c]),
+            ContractionError::RepeatedIndex { tensor, index } => {
+                lang.format("contracao.indice_repetido", &[index, tensor])
+            }
+            ContractionError::UnknownOutputIndex(c) => {
+                lang.format("contracao.indice_de_saida_desconhecido", &[c])
+            }
+            ContractionError::MissingSize(c) => lang.format("contracao.sem_tamanho", &[c]),
+            ContractionError::TooManyIndices => lang.format("contracao.indices_demais", &[]),
+            ContractionError::TooManyTensors(n) => {
+                lang.format("contracao.tensores_demais", &[n, &MAXIMO_EXAUSTIVO])
+            }
+            ContractionError::Overflow(erro) => erro.message(lang),
+        }
+    }
+}
+
+impl fmt::Display for ContractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for ContractionError {}
+
+/// Uma rede de tensores: os índices de cada entrada e os da saída.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Einsum {
+    pub inputs: Vec<Vec<char>>,
+    pub output: Vec<char>,
+}
+
+impl Einsum {
+    /// Lê `ij,jk->ik`. Sem `->`, a saída são os índices que aparecem uma vez
+    /// só, em ordem alfabética, como no `numpy.einsum`.
+    pub fn parse(spec: &str) -> Result<Self, ContractionError> {
+        let (entradas, saida) = match spec.split_once("->") {
+            Some((entradas, saida)) => (entradas, Some(saida)),
+            None => (spec, None),
+        };
+        let indices = |texto: &str| -> Result<Vec<char>, ContractionError> {
+            texto
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .map(|c| if c.is_alphabetic() { Ok(c) } else { Err(ContractionError::InvalidIndex(c)) })
+                .collect()
+        };
+        if entradas.trim().is_empty() {
+            return Err(ContractionError::Empty);
+        }
+        let inputs = entradas.split(',').map(indices).collect::<Result<Vec<_>, _>>()?;
+        for (tensor, tensor_indices) in (1..).zip(&inputs) {
+            for (posicao, &index) in tensor_indices.iter().enumerate() {
+                if tensor_indices[..posicao].contains(&index) {
+                    return Err(ContractionError::RepeatedIndex { tensor, index });
+                }
+            }
+        }
+        let output = match saida {
+            Some(saida) => {
+                let output = indices(saida)?;
+                if let Some(&c) = output.iter().find(|c| !inputs.iter().any(|t| t.contains(c))) {
+                    return Err(ContractionError::UnknownOutputIndex(c));
+                }
+                output
+            }
+            None => {
+                let mut unicos: Vec<char> = inputs
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|c| inputs.iter().flatten().filter(|d| *d == c).count() == 1)
+                    .collect();
+                unicos.sort();
+                unicos
+            }
+        };
+        Ok(Einsum { inputs, output })
+    }
+
+    /// A cadeia de matrizes com dimensões `p` como rede: Aᵢ tem os índices
+    /// (xᵢ₋₁, xᵢ) e a saída é (x₀, xₙ). Devolve também os tamanhos.
+    pub fn from_chain(p: &[usize]) -> (Self, HashMap<char, usize>) {
+        assert!(p.len() >= 2 && p.len() <= 64, "a cadeia precisa ter entre 1 e 63 matrizes");
+        // Letras gregas e latinas dão índices suficientes
+        let letras: Vec<char> = ('a'..='z').chain('A'..='Z').chain('α'..='ω').take(p.len()).collect();
+        let inputs = letras.windows(2).map(|par| par.to_vec()).collect();
+        let output = vec![letras[0], letras[p.len() - 1]];
+        let tamanhos = letras.iter().copied().zip(p.iter().copied()).collect();
+        (Einsum { inputs, output }, tamanhos)
+    }
+}
+
+/// Ordem de contração: as folhas são os tensores de entrada (a partir de 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractionTree {
+    Tensor(usize),
+    Contract(Box<ContractionTree>, Box<ContractionTree>),
+}
+
+impl ContractionTree {
+    fn contrai(esquerda: ContractionTree, direita: ContractionTree) -> Self {
+        ContractionTree::Contract(Box::new(esquerda), Box::new(direita))
+    }
+
+    /// Ordem totalmente parentizada com os nomes dos tensores (`names[i - 1]`
+    /// para o tensor i), no formato de
+    /// [`Notation::FullyParenthesized`](crate::Notation::FullyParenthesized).
+    pub fn render<S: AsRef<str>>(&self, names: &[S]) -> String {
+        match self {
+            ContractionTree::Tensor(i) => names[i - 1].as_ref().to_owned(),
+            ContractionTree::Contract(esquerda, direita) => {
+                format!("({}{})", esquerda.render(names), direita.render(names))
+            }
+        }
+    }
+}
+
+/// Custo total da ordem escolhida e a ordem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractionPath<C> {
+    pub cost: C,
+    pub tree: ContractionTree,
+}
+
+// A rede com os índices trocados por bits: tensores[t] tem um bit por índice
+struct Rede {
+    tensores: Vec<u64>,
+    saida: u64,
+    tamanhos: Vec<usize>,
+}
+
+impl Rede {
+    fn nova(einsum: &Einsum, sizes: &HashMap<char, usize>) -> Result<Self, ContractionError> {
+        if einsum.inputs.is_empty() {
+            return Err(ContractionError::Empty);
+        }
+        let mut letras: Vec<char> = Vec::new();
+        for &c in einsum.inputs.iter().flatten() {
+            if !letras.contains(&c) {
+                letras.push(c);
+            }
+        }
+        if letras.len() > 64 {
+            return Err(ContractionError::TooManyIndices);
+        }
+        let tamanhos = letras
+            .iter()
+            .map(|c| sizes.get(c).copied().ok_or(ContractionError::MissingSize(*c)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mascara = |indices: &[char]| {
+            indices.iter().fold(0u64, |m, c| match letras.iter().position(|l| l == c) {
+                Some(bit) => m | 1 << bit,
+                None => m,
+            })
+        };
+        Ok(Rede {
+            tensores: einsum.inputs.iter().map(|t| mascara(t)).collect(),
+            saida: mascara(&einsum.output),
+            tamanhos,
+        })
+    }
+
+    // Produto dos tamanhos dos índices da máscara
+    fn volume<C: Cost>(&self, mascara: u64) -> Option<C> {
+        let mut volume = C::from_dimension(1)?;
+        let mut resto = mascara;
+        while resto != 0 {
+            let bit = resto.trailing_zeros() as usize;
+            volume = volume.checked_mul(C::from_dimension(self.tamanhos[bit])?)?;
+            resto &= resto - 1;
+        }
+        Some(volume)
+    }
+}
+
+fn otima<C: Cost>(rede: &Rede) -> Result<Option<(C, ContractionTree)>, ContractionError> {
+    let n = rede.tensores.len();
+    if n > MAXIMO_EXAUSTIVO {
+        return Err(ContractionError::TooManyTensors(n));
+    }
+    let todos = (1u32 << n) - 1;
+    // Índices presentes em cada subconjunto de tensores
+    let mut uniao = vec![0u64; 1 << n];
+    for s in 1..=todos {
+        let menor = s.trailing_zeros() as usize;
+        uniao[s as usize] = uniao[(s & (s - 1)) as usize] | rede.tensores[menor];
+    }
+    // Índices que sobram depois de contrair o subconjunto
+    let restantes = |s: u32| uniao[s as usize] & (uniao[(todos ^ s) as usize] | rede.saida);
+
+    // custo[s] e a divisão que o atinge; None em custo é "não cabe em C"
+    let mut custo: Vec<Option<C>> = vec![None; 1 << n];
+    let mut divisao = vec![0u32; 1 << n];
+    for s in 1..=todos {
+        if s.is_power_of_two() {
+            custo[s as usize] = Some(C::ZERO);
+            continue;
+        }
+        // Só as partes que contêm o menor tensor de s, para ver cada divisão uma vez
+        let menor = s & s.wrapping_neg();
+        let mut parte = (s - 1) & s;
+        while parte > 0 {
+            if parte & menor != 0 {
+                let resto = s ^ parte;
+                let q = custo[parte as usize].zip(custo[resto as usize]).and_then(|(a, b)| {
+                    rede.volume::<C>(restantes(parte) | restantes(resto))?.checked_add(a)?.checked_add(b)
+                });
+                if let Some(q) = q
+                    && custo[s as usize].is_none_or(|atual| q < atual)
+                {
+                    custo[s as usize] = Some(q);
+                    divisao[s as usize] = parte;
+                }
+            }
+            parte = (parte - 1) & s;
+        }
+    }
+
+    fn monta(s: u32, divisao: &[u32]) -> ContractionTree {
+        if s.is_power_of_two() {
+            return ContractionTree::Tensor(s.trailing_zeros() as usize + 1);
+        }
+        let parte = divisao[s as usize];
+        ContractionTree::contrai(monta(parte, divisao), monta(s ^ parte, divisao))
+    }
+    // Sem custo não há divisão registrada para montar a árvore
+    Ok(custo[todos as usize].map(|c| (c, monta(todos, &divisao))))
+}
+
+fn gulosa<C: Cost>(rede: &Rede) -> Option<(C, ContractionTree)> {
+    // Cada tensor atual com os índices que ainda tem e a árvore que o formou
+    let mut atuais: Vec<(u64, ContractionTree)> = (1..)
+        .zip(&rede.tensores)
+        .map(|(i, &m)| (m, ContractionTree::Tensor(i)))
+        .collect();
+    let mut total = C::ZERO;
+    while atuais.len() > 1 {
+        let mut melhor: Option<(bool, C, usize, usize)> = None;
+        for a in 0..atuais.len() {
+            for b in a + 1..atuais.len() {
+                // Pares cujo custo não cabe em C ficam de fora, como na busca exaustiva
+                let Some(custo) = rede.volume::<C>(atuais[a].0 | atuais[b].0) else {
+                    continue;
+                };
+                // Pares que compartilham índices vêm antes de produtos externos
+                let externo = atuais[a].0 & atuais[b].0 == 0;
+                if melhor.is_none_or(|(e, c, _, _)| (externo, custo) < (e, c)) {
+                    melhor = Some((externo, custo, a, b));
+                }
+            }
+        }
+        let (_, custo, a, b) = melhor?;
+        total = total.checked_add(custo)?;
+        let (mb, tb) = atuais.remove(b);
+        let (ma, ta) = atuais.remove(a);
+        let fora = atuais.iter().fold(rede.saida, |m, (t, _)| m | t);
+        atuais.insert(a, ((ma | mb) & fora, ContractionTree::contrai(ta, tb)));
+    }
+    let (_, arvore) = atuais.pop().unwrap();
+    Some((total, arvore))
+}
+
+/// Melhor ordem de contração da rede `einsum` com os tamanhos de índice
+/// `sizes`, pela estratégia escolhida.
+pub fn contraction_path<C: Cost>(
+    einsum: &Einsum,
+    sizes: &HashMap<char, usize>,
+    strategy: PathStrategy,
+) -> Result<ContractionPath<C>, ContractionError> {
+    let rede = Rede::nova(einsum, sizes)?;
+    let exaustiva = match strategy {
+        PathStrategy::Optimal => true,
+        PathStrategy::Greedy => false,
+        PathStrategy::Auto => rede.tensores.len() <= OPTIMAL_LIMIT,
+    };
+    let estouro = ContractionError::Overflow(CostOverflow::of::<C>());
+    let caminho = if exaustiva { otima::<C>(&rede)? } else { gulosa::<C>(&rede) };
+    let (cost, tree) = caminho.ok_or(estouro)?;
+    Ok(ContractionPath { cost, tree })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{CLRS, ESTOURA_U64, dimensoes};
+    use crate::{Notation, ParenTree, multiplicacao_de_matrizes};
+
+    fn tamanhos(pares: &[(char, usize)]) -> HashMap<char, usize> {
+        pares.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let einsum = Einsum::parse("bij, bjk -> bik").unwrap();
+        assert_eq!(einsum.inputs, [vec!['b', 'i', 'j'], vec!['b', 'j', 'k']]);
+        assert_eq!(einsum.output, ['b', 'i', 'k']);
+        // Saída implícita: índices que aparecem uma vez só, em ordem
+        assert_eq!(Einsum::parse("kj,ji").unwrap().output, ['i', 'k']);
+
+        assert_eq!(Einsum::parse(""), Err(ContractionError::Empty));
+        assert_eq!(Einsum::parse("i1,1j"), Err(ContractionError::InvalidIndex('1')));
+        assert_eq!(Einsum::parse("ii->i"), Err(ContractionError::RepeatedIndex { tensor: 1, index: 'i' }));
+        assert_eq!(Einsum::parse("ij->k"), Err(ContractionError::UnknownOutputIndex('k')));
+    }
+
+    #[test]
+    fn test_reduces_to_matrix_chain() {
+        let p = CLRS;
+        let (einsum, sizes) = Einsum::from_chain(&p);
+        let caminho = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Optimal).unwrap();
+        assert_eq!(caminho.cost, 15125);
+        let nomes: Vec<String> = (1..=6).map(|i| format!("A{i}")).collect();
+        let (_, s) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+        assert_eq!(
+            caminho.tree.render(&nomes),
+            ParenTree::from_splits(&s, 1, 6).render(&p, Notation::FullyParenthesized)
+        );
+    }
+
+    #[test]
+    fn test_random_chains_match_dynamic_programming() {
+        let mut estado: u64 = 0x9FB2_1C65_1E98_DF25;
+        for n in 1..=10 {
+            for _ in 0..10 {
+                let p = dimensoes(&mut estado, n, 50);
+                let (einsum, sizes) = Einsum::from_chain(&p);
+                let (m, _) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+                let otimo = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Optimal).unwrap();
+                assert_eq!(Some(otimo.cost), m.get(1, n), "p = {p:?}");
+                let guloso = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Greedy).unwrap();
+                assert!(guloso.cost >= otimo.cost, "p = {p:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_vector_and_batch() {
+        // A·B·v: multiplicar B·v primeiro evita o produto de matrizes
+        let einsum = Einsum::parse("ij,jk,k->i").unwrap();
+        let sizes = tamanhos(&[('i', 100), ('j', 100), ('k', 100)]);
+        let caminho = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Optimal).unwrap();
+        assert_eq!(caminho.cost, 2 * 100 * 100);
+        assert_eq!(caminho.tree.render(&["A", "B", "v"]), "(A(Bv))");
+
+        // Lote de b produtos: cada contração custa b vezes a da matriz
+        let einsum = Einsum::parse("bij,bjk,bkl->bil").unwrap();
+        let sizes = tamanhos(&[('b', 8), ('i', 10), ('j', 30), ('k', 5), ('l', 60)]);
+        let caminho = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Auto).unwrap();
+        assert_eq!(caminho.cost, 8 * (10 * 30 * 5 + 10 * 5 * 60));
+    }
+
+    #[test]
+    fn test_greedy_on_large_network() {
+        // Cadeia longa de matrizes com um vetor na ponta: a gulosa contrai do
+        // vetor para trás e só faz produtos matriz-vetor
+        let n = 40;
+        let indice = |t: u32| char::from_u32(0x100 + t).unwrap();
+        let mut spec: Vec<String> = (0..n).map(|t| format!("{}{}", indice(t), indice(t + 1))).collect();
+        spec.push(indice(n).to_string());
+        let einsum = Einsum::parse(&spec.join(",")).unwrap();
+        let sizes: HashMap<char, usize> = einsum.inputs.iter().flatten().map(|&c| (c, 20)).collect();
+        let caminho = contraction_path::<u64>(&einsum, &sizes, PathStrategy::Auto).unwrap();
+        assert_eq!(caminho.cost, n as u64 * 20 * 20);
+        assert_eq!(
+            contraction_path::<u64>(&einsum, &sizes, PathStrategy::Optimal),
+            Err(ContractionError::TooManyTensors(n as usize + 1))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let einsum = Einsum::parse("ij,jk").unwrap();
+        let sizes = tamanhos(&[('i', 2), ('j', 3)]);
+        assert_eq!(
+            contraction_path::<u64>(&einsum, &sizes, PathStrategy::Auto),
+            Err(ContractionError::MissingSize('k'))
+        );
+        let b = ESTOURA_U64;
+        let sizes = tamanhos(&[('i', b), ('j', b), ('k', b)]);
+        for estrategia in [PathStrategy::Optimal, PathStrategy::Greedy] {
+            assert_eq!(
+                contraction_path::<u64>(&einsum, &sizes, estrategia),
+                Err(ContractionError::Overflow(CostOverflow::of::<u64>()))
+            );
+        }
+
+        // Contrair ai com bj estoura u64, mas a rede cabe contraindo a e b antes
+        let einsum = Einsum::parse("ai,a,bj,b->ij").unwrap();
+        let b = 1 << 40;
+        let sizes = tamanhos(&[('a', b), ('b', b), ('i', 2), ('j', 2)]);
+        for estrategia in [PathStrategy::Optimal, PathStrategy::Greedy] {
+            let caminho = contraction_path::<u64>(&einsum, &sizes, estrategia).unwrap();
+            assert_eq!(caminho.cost, 2 * (2 * b as u64) + 4);
+        }
+
+        let erro = ContractionError::RepeatedIndex { tensor: 2, index: 'j' };
+        assert_eq!(erro.to_string(), "o índice j aparece duas vezes no tensor 2");
+        assert_eq!(erro.message(Lang::En), "the index j appears twice in tensor 2");
+    }
+}
@@ -0,0 +1,80 @@
+use std::{any, fmt};
+
+use mensagens::Lang;
+
+/// Tipo numérico usado para contar multiplicações escalares. As operações são
+/// verificadas para que um custo grande demais vire um erro em vez de dar a
+/// volta silenciosamente, como acontece com `usize` em builds de release.
+pub trait Cost: Copy + Ord + fmt::Debug + fmt::Display {
+    const ZERO: Self;
+
+    fn from_dimension(d: usize) -> Option<Self>;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),*) => {$(
+        impl Cost for $t {
+            const ZERO: Self = 0;
+
+            fn from_dimension(d: usize) -> Option<Self> {
+                <$t>::try_from(d).ok()
+            }
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_cost!(u32, u64, u128, usize);
+
+/// Custo p[i-1]·p[k]·p[j] de multiplicar uma matriz p[i-1]×p[k] por uma
+/// p[k]×p[j], ou `None` se ele não cabe em `C`.
+pub fn scalar_product<C: Cost>(rows: usize, inner: usize, cols: usize) -> Option<C> {
+    C::from_dimension(rows)?
+        .checked_mul(C::from_dimension(inner)?)?
+        .checked_mul(C::from_dimension(cols)?)
+}
+
+/// O custo mínimo da cadeia não pode ser representado no tipo de custo escolhido.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostOverflow {
+    pub cost_type: &'static str,
+}
+
+impl CostOverflow {
+    pub fn of<C: Cost>() -> Self {
+        CostOverflow { cost_type: any::type_name::<C>() }
+    }
+
+    pub fn message(&self, lang: Lang) -> String {
+        lang.format("custo.estouro", &[&self.cost_type])
+    }
+}
+
+impl fmt::Display for CostOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for CostOverflow {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_product_overflow() {
+        assert_eq!(scalar_product::<u32>(1 << 10, 1 << 10, 1 << 12), None);
+        assert_eq!(scalar_product::<u64>(1 << 10, 1 << 10, 1 << 12), Some(1 << 32));
+        assert_eq!(scalar_product::<u32>(usize::MAX, 1, 1), None);
+    }
+}
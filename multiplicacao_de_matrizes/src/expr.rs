@@ -0,0 +1,236 @@
+//! Leitura de uma associação escrita à mão, no mesmo formato da notação
+//! [`Notation::FullyParenthesized`](crate::Notation::FullyParenthesized):
+//! `((A1A2)A3)`. Os parênteses externos são opcionais (`(A1A2)A3` também
+//! vale), espaços são ignorados e as matrizes precisam aparecer em ordem,
+//! de A1 até An.
+
+use std::fmt;
+
+use mensagens::Lang;
+
+use crate::{Cost, CostOverflow, ParenTree};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprErrorKind {
+    UnexpectedChar(char),
+    UnexpectedEnd,
+    // `A` sem número depois, ou um número que não cabe em usize
+    InvalidIndex,
+    // As matrizes precisam vir na ordem da cadeia: A1, A2, A3…
+    OutOfOrder { expected: usize, found: usize },
+    // A expressão não cobre a cadeia inteira, ou passa dela
+    WrongLength { expected: usize, found: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    pub kind: ExprErrorKind,
+    // Coluna (a partir de 1, em caracteres) onde o problema foi encontrado
+    pub column: usize,
+}
+
+impl ExprError {
+    pub fn message(&self, lang: Lang) -> String {
+        let motivo = match &self.kind {
+            ExprErrorKind::UnexpectedChar(c) => lang.format("expressao.caractere_inesperado", &[c]),
+            ExprErrorKind::UnexpectedEnd => lang.format("expressao.fim_inesperado", &[]),
+            ExprErrorKind::InvalidIndex => lang.format("expressao.indice_invalido", &[]),
+            ExprErrorKind::OutOfOrder { expected, found } => lang.format("expressao.fora_de_ordem", &[expected, found]),
+            ExprErrorKind::WrongLength { expected, found } => {
+                lang.format("expressao.tamanho_errado", &[expected, found])
+            }
+        };
+        lang.format("expressao.erro_na_coluna", &[&self.column, &motivo])
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Erro de [`cost_of`]: a cadeia não tem nenhuma matriz, a expressão é
+/// inválida ou o custo dela não cabe no tipo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CostOfError {
+    /// `p` precisa de pelo menos duas dimensões, uma matriz.
+    TooFewDimensions,
+    Expr(ExprError),
+    Overflow(CostOverflow),
+}
+
+impl CostOfError {
+    pub fn message(&self, lang: Lang) -> String {
+        match self {
+            CostOfError::TooFewDimensions => lang.format("plano.poucas_dimensoes", &[]),
+            CostOfError::Expr(erro) => erro.message(lang),
+            CostOfError::Overflow(erro) => erro.message(lang),
+        }
+    }
+}
+
+impl fmt::Display for CostOfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for CostOfError {}
+
+struct Leitor {
+    caracteres: Vec<char>,
+    posicao: usize,
+    // Próxima matriz esperada na expressão
+    proxima: usize,
+}
+
+impl Leitor {
+    fn erro(&self, kind: ExprErrorKind) -> ExprError {
+        ExprError { kind, column: self.posicao + 1 }
+    }
+
+    fn pula_espacos(&mut self) {
+        while self.caracteres.get(self.posicao).is_some_and(|c| c.is_whitespace()) {
+            self.posicao += 1;
+        }
+    }
+
+    fn espia(&mut self) -> Option<char> {
+        self.pula_espacos();
+        self.caracteres.get(self.posicao).copied()
+    }
+
+    fn espera(&mut self, esperado: char) -> Result<(), ExprError> {
+        match self.espia() {
+            Some(c) if c == esperado => {
+                self.posicao += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.erro(ExprErrorKind::UnexpectedChar(c))),
+            None => Err(self.erro(ExprErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    // operando := 'A' número | '(' operando operando ')'
+    fn operando(&mut self) -> Result<ParenTree, ExprError> {
+        match self.espia() {
+            Some('(') => {
+                self.posicao += 1;
+                let esquerda = self.operando()?;
+                let direita = self.operando()?;
+                self.espera(')')?;
+                Ok(ParenTree::product(esquerda, direita))
+            }
+            Some('A') => {
+                let inicio = self.posicao;
+                self.posicao += 1;
+                let digitos: String = self.caracteres[self.posicao..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                let indice = digitos.parse::<usize>().map_err(|_| self.erro(ExprErrorKind::InvalidIndex))?;
+                if indice != self.proxima {
+                    self.posicao = inicio;
+                    return Err(self.erro(ExprErrorKind::OutOfOrder { expected: self.proxima, found: indice }));
+                }
+                self.posicao += digitos.len();
+                self.proxima += 1;
+                Ok(ParenTree::Leaf(indice))
+            }
+            Some(c) => Err(self.erro(ExprErrorKind::UnexpectedChar(c))),
+            None => Err(self.erro(ExprErrorKind::UnexpectedEnd)),
+        }
+    }
+}
+
+impl ParenTree {
+    /// Lê uma associação como `((A1A2)A3)`. As matrizes precisam aparecer em
+    /// ordem a partir de A1; quantas são fica a cargo de quem chama.
+    pub fn parse(expr: &str) -> Result<Self, ExprError> {
+        let mut leitor = Leitor { caracteres: expr.chars().collect(), posicao: 0, proxima: 1 };
+        let mut arvore = leitor.operando()?;
+        // Sem os parênteses externos sobra um segundo operando
+        if leitor.espia().is_some() {
+            let direita = leitor.operando()?;
+            arvore = ParenTree::product(arvore, direita);
+        }
+        if let Some(c) = leitor.espia() {
+            return Err(leitor.erro(ExprErrorKind::UnexpectedChar(c)));
+        }
+        Ok(arvore)
+    }
+}
+
+/// Quantidade de multiplicações escalares da associação `expr` da cadeia com
+/// dimensões `p`, para comparar uma ordem escrita à mão com a ótima.
+pub fn cost_of<C: Cost>(p: &[usize], expr: &str) -> Result<C, CostOfError> {
+    if p.len() < 2 {
+        return Err(CostOfError::TooFewDimensions);
+    }
+    let arvore = ParenTree::parse(expr).map_err(CostOfError::Expr)?;
+    let n = p.len() - 1;
+    let (_, ultima) = arvore.range();
+    if ultima != n {
+        return Err(CostOfError::Expr(ExprError {
+            kind: ExprErrorKind::WrongLength { expected: n, found: ultima },
+            column: expr.chars().count(),
+        }));
+    }
+    arvore.cost(p).ok_or(CostOfError::Overflow(CostOverflow::of::<C>()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Notation;
+    use crate::test_support::{CLRS, ESTOURA_U64};
+
+    #[test]
+    fn test_parse_round_trip() {
+        for expr in ["A1", "(A1A2)", "((A1(A2A3))((A4A5)A6))", "(((A1A2)A3)A4)"] {
+            let arvore = ParenTree::parse(expr).unwrap();
+            assert_eq!(arvore.render(&CLRS, Notation::FullyParenthesized), expr);
+        }
+        // Parênteses externos e espaços são opcionais
+        assert_eq!(ParenTree::parse(" (A1 A2) A3 ").unwrap(), ParenTree::parse("((A1A2)A3)").unwrap());
+    }
+
+    #[test]
+    fn test_cost_of() {
+        assert_eq!(cost_of::<u64>(&CLRS, "((A1(A2A3))((A4A5)A6))"), Ok(15125));
+        // Da esquerda para a direita o resultado parcial sempre tem 30 linhas
+        assert_eq!(
+            cost_of::<u64>(&CLRS, "(((((A1A2)A3)A4)A5)A6)"),
+            Ok(30 * 35 * 15 + 30 * 15 * 5 + 30 * 5 * 10 + 30 * 10 * 20 + 30 * 20 * 25)
+        );
+        assert_eq!(cost_of::<u64>(&[4, 3], "A1"), Ok(0));
+    }
+
+    #[test]
+    fn test_errors() {
+        let erro = |expr: &str| match cost_of::<u64>(&CLRS[..4], expr) {
+            Err(CostOfError::Expr(erro)) => erro,
+            outro => panic!("esperava erro na expressão {expr:?}, veio {outro:?}"),
+        };
+        assert_eq!(erro("((A1A3)A2)").kind, ExprErrorKind::OutOfOrder { expected: 2, found: 3 });
+        assert_eq!(erro("((A1A3)A2)").column, 5);
+        assert_eq!(erro("(A1A2").kind, ExprErrorKind::UnexpectedEnd);
+        assert_eq!(erro("(A1A2A3)").kind, ExprErrorKind::UnexpectedChar('A'));
+        assert_eq!(erro("(A1B2)").kind, ExprErrorKind::UnexpectedChar('B'));
+        assert_eq!(erro("(A1A)").kind, ExprErrorKind::InvalidIndex);
+        assert_eq!(erro("(A1A2)").kind, ExprErrorKind::WrongLength { expected: 3, found: 2 });
+        assert_eq!(erro("A1A2A3").kind, ExprErrorKind::UnexpectedChar('A'));
+
+        assert_eq!(cost_of::<u64>(&[], "A1"), Err(CostOfError::TooFewDimensions));
+        assert_eq!(cost_of::<u64>(&[4], "A1"), Err(CostOfError::TooFewDimensions));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let b = ESTOURA_U64;
+        assert_eq!(cost_of::<u64>(&[b, b, b], "A1A2"), Err(CostOfError::Overflow(CostOverflow::of::<u64>())));
+    }
+}
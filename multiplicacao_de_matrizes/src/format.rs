@@ -0,0 +1,167 @@
+//! Índices e expoentes em Unicode (`A₁₀ₓ₂₀`, `x²`) com alternativa em
+//! ASCII (`A_10x20`) para terminais que não mostram UTF-8.
+//!
+//! O Unicode só tem subscritos e sobrescritos para parte das letras, então
+//! [`subscript`] e [`superscript`] devolvem `None` quando algum caractere não
+//! tem versão, e quem chama decide o que fazer (em geral, cair no ASCII).
+
+use std::env;
+
+// Dígitos, sinais e letras com subscrito no Unicode
+const SUBSCRITOS: &[(char, char)] = &[
+    ('0', '₀'), ('1', '₁'), ('2', '₂'), ('3', '₃'), ('4', '₄'),
+    ('5', '₅'), ('6', '₆'), ('7', '₇'), ('8', '₈'), ('9', '₉'),
+    ('+', '₊'), ('-', '₋'), ('−', '₋'), ('=', '₌'), ('(', '₍'), (')', '₎'),
+    ('a', 'ₐ'), ('e', 'ₑ'), ('h', 'ₕ'), ('i', 'ᵢ'), ('j', 'ⱼ'), ('k', 'ₖ'),
+    ('l', 'ₗ'), ('m', 'ₘ'), ('n', 'ₙ'), ('o', 'ₒ'), ('p', 'ₚ'), ('r', 'ᵣ'),
+    ('s', 'ₛ'), ('t', 'ₜ'), ('u', 'ᵤ'), ('v', 'ᵥ'), ('x', 'ₓ'),
+    ('β', 'ᵦ'), ('γ', 'ᵧ'), ('ρ', 'ᵨ'), ('φ', 'ᵩ'), ('χ', 'ᵪ'),
+];
+
+// Dígitos, sinais e letras com sobrescrito no Unicode. Faltam q e as
+// maiúsculas C, F, Q, S, X, Y e Z.
+const SOBRESCRITOS: &[(char, char)] = &[
+    ('0', '⁰'), ('1', '¹'), ('2', '²'), ('3', '³'), ('4', '⁴'),
+    ('5', '⁵'), ('6', '⁶'), ('7', '⁷'), ('8', '⁸'), ('9', '⁹'),
+    ('+', '⁺'), ('-', '⁻'), ('−', '⁻'), ('=', '⁼'), ('(', '⁽'), (')', '⁾'),
+    ('a', 'ᵃ'), ('b', 'ᵇ'), ('c', 'ᶜ'), ('d', 'ᵈ'), ('e', 'ᵉ'), ('f', 'ᶠ'),
+    ('g', 'ᵍ'), ('h', 'ʰ'), ('i', 'ⁱ'), ('j', 'ʲ'), ('k', 'ᵏ'), ('l', 'ˡ'),
+    ('m', 'ᵐ'), ('n', 'ⁿ'), ('o', 'ᵒ'), ('p', 'ᵖ'), ('r', 'ʳ'), ('s', 'ˢ'),
+    ('t', 'ᵗ'), ('u', 'ᵘ'), ('v', 'ᵛ'), ('w', 'ʷ'), ('x', 'ˣ'), ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+    ('A', 'ᴬ'), ('B', 'ᴮ'), ('D', 'ᴰ'), ('E', 'ᴱ'), ('G', 'ᴳ'), ('H', 'ᴴ'),
+    ('I', 'ᴵ'), ('J', 'ᴶ'), ('K', 'ᴷ'), ('L', 'ᴸ'), ('M', 'ᴹ'), ('N', 'ᴺ'),
+    ('O', 'ᴼ'), ('P', 'ᴾ'), ('R', 'ᴿ'), ('T', 'ᵀ'), ('U', 'ᵁ'), ('V', 'ⱽ'),
+    ('W', 'ᵂ'),
+    ('α', 'ᵅ'), ('β', 'ᵝ'), ('γ', 'ᵞ'), ('δ', 'ᵟ'), ('θ', 'ᶿ'), ('φ', 'ᵠ'),
+    ('χ', 'ᵡ'),
+];
+
+fn procura(tabela: &[(char, char)], c: char) -> Option<char> {
+    tabela.iter().find(|(normal, _)| *normal == c).map(|(_, trocado)| *trocado)
+}
+
+pub fn subscript_char(c: char) -> Option<char> {
+    procura(SUBSCRITOS, c)
+}
+
+pub fn superscript_char(c: char) -> Option<char> {
+    procura(SOBRESCRITOS, c)
+}
+
+/// `text` todo em subscrito, ou `None` se algum caractere não tem subscrito.
+pub fn subscript(text: &str) -> Option<String> {
+    text.chars().map(subscript_char).collect()
+}
+
+/// `text` todo em sobrescrito, ou `None` se algum caractere não tem sobrescrito.
+pub fn superscript(text: &str) -> Option<String> {
+    text.chars().map(superscript_char).collect()
+}
+
+/// Um número em subscrito. Todo dígito tem subscrito, então nunca falha.
+pub fn format_index(x: usize) -> String {
+    x.to_string().chars().filter_map(subscript_char).collect()
+}
+
+/// Nome da matriz `rows`×`cols`: `A₁₀ₓ₂₀` em Unicode ou `A_10x20` em ASCII.
+pub fn matrix_label(rows: usize, cols: usize, unicode: bool) -> String {
+    if unicode {
+        format!("A{}ₓ{}", format_index(rows), format_index(cols))
+    } else {
+        format!("A_{rows}x{cols}")
+    }
+}
+
+/// Se a saída aceita UTF-8, pelo locale: a primeira variável entre `LC_ALL`,
+/// `LC_CTYPE` e `LANG` que estiver definida (e não vazia) precisa indicar
+/// UTF-8. Sem nenhuma delas vale o locale `C`, que só garante ASCII.
+pub fn utf8_output() -> bool {
+    utf8_nas_variaveis(|nome| env::var(nome).ok())
+}
+
+fn utf8_nas_variaveis(var: impl Fn(&str) -> Option<String>) -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|nome| var(nome))
+        .find(|valor| !valor.is_empty())
+        .is_some_and(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_subscript_mappings() {
+        let casos = [
+            ("0123456789", "₀₁₂₃₄₅₆₇₈₉"),
+            ("+-−=()", "₊₋₋₌₍₎"),
+            ("aehijklmnoprstuvx", "ₐₑₕᵢⱼₖₗₘₙₒₚᵣₛₜᵤᵥₓ"),
+            ("βγρφχ", "ᵦᵧᵨᵩᵪ"),
+        ];
+        for (normal, esperado) in casos {
+            assert_eq!(subscript(normal).as_deref(), Some(esperado));
+        }
+        // Cada entrada da tabela aparece em algum caso
+        let testados: usize = casos.iter().map(|(normal, _)| normal.chars().count()).sum();
+        assert_eq!(testados, SUBSCRITOS.len());
+    }
+
+    #[test]
+    fn test_superscript_mappings() {
+        let casos = [
+            ("0123456789", "⁰¹²³⁴⁵⁶⁷⁸⁹"),
+            ("+-−=()", "⁺⁻⁻⁼⁽⁾"),
+            ("abcdefghijklmnoprstuvwxyz", "ᵃᵇᶜᵈᵉᶠᵍʰⁱʲᵏˡᵐⁿᵒᵖʳˢᵗᵘᵛʷˣʸᶻ"),
+            ("ABDEGHIJKLMNOPRTUVW", "ᴬᴮᴰᴱᴳᴴᴵᴶᴷᴸᴹᴺᴼᴾᴿᵀᵁⱽᵂ"),
+            ("αβγδθφχ", "ᵅᵝᵞᵟᶿᵠᵡ"),
+        ];
+        for (normal, esperado) in casos {
+            assert_eq!(superscript(normal).as_deref(), Some(esperado));
+        }
+        let testados: usize = casos.iter().map(|(normal, _)| normal.chars().count()).sum();
+        assert_eq!(testados, SOBRESCRITOS.len());
+    }
+
+    #[test]
+    fn test_missing_mappings() {
+        assert_eq!(subscript("b"), None);
+        assert_eq!(subscript("A"), None);
+        assert_eq!(subscript("x2y"), None);
+        assert_eq!(superscript("q"), None);
+        assert_eq!(superscript("X"), None);
+        assert_eq!(superscript(" "), None);
+        assert_eq!(subscript(""), Some(String::new()));
+    }
+
+    #[test]
+    fn test_format_index_and_label() {
+        assert_eq!(format_index(0), "₀");
+        assert_eq!(format_index(1234567890), "₁₂₃₄₅₆₇₈₉₀");
+        assert_eq!(format_index(usize::MAX), subscript(&usize::MAX.to_string()).unwrap());
+        assert_eq!(matrix_label(10, 20, true), "A₁₀ₓ₂₀");
+        assert_eq!(matrix_label(10, 20, false), "A_10x20");
+    }
+
+    #[test]
+    fn test_utf8_detection() {
+        let ambiente = |pares: &[(&str, &str)]| {
+            let variaveis: HashMap<String, String> =
+                pares.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            utf8_nas_variaveis(|nome| variaveis.get(nome).cloned())
+        };
+        assert!(!ambiente(&[]));
+        assert!(ambiente(&[("LANG", "pt_BR.UTF-8")]));
+        assert!(ambiente(&[("LANG", "en_US.utf8")]));
+        assert!(!ambiente(&[("LANG", "C")]));
+        assert!(!ambiente(&[("LANG", "pt_BR.ISO-8859-1")]));
+        // LC_ALL tem prioridade; vazio não conta
+        assert!(!ambiente(&[("LANG", "pt_BR.UTF-8"), ("LC_ALL", "POSIX")]));
+        assert!(ambiente(&[("LANG", "C"), ("LC_CTYPE", "C.UTF-8"), ("LC_ALL", "")]));
+    }
+}
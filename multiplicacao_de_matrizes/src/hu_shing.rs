@@ -0,0 +1,483 @@
+//! Algoritmo de Hu e Shing para a ordem ótima de uma cadeia de matrizes em
+//! O(n log n).
+//!
+//! A cadeia com dimensões p₀, …, pₙ vira um polígono convexo cujos vértices
+//! têm pesos p₀, …, pₙ; cada associação corresponde a uma triangulação, e o
+//! custo de um triângulo é o produto dos pesos dos seus vértices. Com o
+//! polígono rodado para que o vértice de menor peso V₀ venha primeiro:
+//!
+//! - os únicos arcos "horizontais" que podem aparecer numa triangulação ótima
+//!   são os arcos potenciais Vᵢ–Vⱼ em que todos os vértices entre i e j pesam
+//!   mais que ambos os extremos. Eles formam exatamente a árvore cartesiana
+//!   (de mínimo) da sequência de pesos, e o arco do nó m vai do menor vértice
+//!   à esquerda de m até o menor vértice à direita;
+//! - o resto da triangulação são leques: cada face delimitada pelos arcos
+//!   mantidos é triangulada a partir do seu vértice de menor peso.
+//!
+//! Um arco h cujo ápice (o vértice de onde sai o leque logo abaixo dele) pesa
+//! w é mantido quando C(h) + w·wᵢwⱼ ≤ H_h(w), onde C(h) é o custo ótimo do
+//! subpolígono acima de h e H_h(w) o custo de cobri-lo com o leque do ápice.
+//! Isso define o peso de suporte T(h): o arco é mantido se, e somente se, o
+//! ápice pesa pelo menos T(h). Os arcos ainda mantidos de cada subárvore ficam
+//! num heap esquerdista ordenado por T, e descartar um arco funde o heap dele
+//! no do pai; cada arco entra e sai de um heap uma única vez.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{Cost, CostOverflow, ParenTree, PlanError};
+
+// Fração não negativa num/den (den > 0)
+#[derive(Debug, Clone, Copy)]
+struct Fracao {
+    num: u128,
+    den: u128,
+}
+
+impl Fracao {
+    fn inteiro(x: u128) -> Self {
+        Fracao { num: x, den: 1 }
+    }
+}
+
+// Compara a/b com c/d sem multiplicar (o que poderia estourar u128), pelo
+// mesmo raciocínio do algoritmo de Euclides sobre frações contínuas
+fn compara(a: Fracao, b: Fracao) -> Ordering {
+    let (qa, ra) = (a.num / a.den, a.num % a.den);
+    let (qb, rb) = (b.num / b.den, b.num % b.den);
+    match qa.cmp(&qb) {
+        Ordering::Equal => match (ra, rb) {
+            (0, 0) => Ordering::Equal,
+            (0, _) => Ordering::Less,
+            (_, 0) => Ordering::Greater,
+            // ra/a.den < rb/b.den se, e somente se, a.den/ra > b.den/rb
+            _ => compara(Fracao { num: b.den, den: rb }, Fracao { num: a.den, den: ra }),
+        },
+        ordem => ordem,
+    }
+}
+
+impl PartialEq for Fracao {
+    fn eq(&self, other: &Self) -> bool {
+        compara(*self, *other) == Ordering::Equal
+    }
+}
+
+impl Eq for Fracao {}
+
+impl PartialOrd for Fracao {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fracao {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compara(*self, *other)
+    }
+}
+
+// Um arco potencial, identificado pelo nó m da árvore cartesiana
+#[derive(Debug, Clone)]
+struct Arco {
+    i: usize,
+    j: usize,
+    esquerdo: Option<usize>, // Arco (i, m), se m não é vizinho de i
+    direito: Option<usize>,  // Arco (m, j), se m não é vizinho de j
+    x: usize,                // Extremo de menor peso
+    custo: u128,             // C(h)
+    suporte: Fracao,         // T(h)
+    // H_h(w) = fixo + w·linear, válido para w ≤ T(h)
+    fixo: u128,
+    linear: u128,
+    escondido: Option<usize>, // Heap dos arcos ainda mantidos acima de h
+}
+
+// Heap esquerdista de máximo sobre os arcos, ordenado pelo peso de suporte.
+// Cada arco está em no máximo um heap, então os nós são os próprios arcos.
+struct Heaps {
+    esquerda: Vec<Option<usize>>,
+    direita: Vec<Option<usize>>,
+    posto: Vec<usize>,
+}
+
+impl Heaps {
+    fn new(n: usize) -> Self {
+        Heaps {
+            esquerda: vec![None; n],
+            direita: vec![None; n],
+            posto: vec![1; n],
+        }
+    }
+
+    fn posto(&self, h: Option<usize>) -> usize {
+        h.map_or(0, |h| self.posto[h])
+    }
+
+    fn une(&mut self, a: Option<usize>, b: Option<usize>, arcos: &[Arco]) -> Option<usize> {
+        let (a, b) = match (a, b) {
+            (None, h) | (h, None) => return h,
+            (Some(a), Some(b)) => (a, b),
+        };
+        let (raiz, outro) = if arcos[a].suporte >= arcos[b].suporte { (a, b) } else { (b, a) };
+        let direita = self.une(self.direita[raiz], Some(outro), arcos);
+        self.direita[raiz] = direita;
+        if self.posto(self.esquerda[raiz]) < self.posto(direita) {
+            self.direita[raiz] = self.esquerda[raiz];
+            self.esquerda[raiz] = direita;
+        }
+        self.posto[raiz] = self.posto(self.direita[raiz]) + 1;
+        Some(raiz)
+    }
+
+    fn remove_topo(&mut self, h: usize, arcos: &[Arco]) -> Option<usize> {
+        let (esquerda, direita) = (self.esquerda[h].take(), self.direita[h].take());
+        self.posto[h] = 1;
+        self.une(esquerda, direita, arcos)
+    }
+}
+
+// Estado da face abaixo de um arco enquanto os arcos do heap são descartados
+struct Face {
+    heap: Option<usize>,
+    fixo: u128,
+    linear: u128,
+}
+
+struct Poligono<'a> {
+    p: &'a [usize],
+    inicio: usize, // Índice original do vértice de menor peso
+}
+
+impl Poligono<'_> {
+    fn vertices(&self) -> usize {
+        self.p.len()
+    }
+
+    // Posições 0..=n+1 na sequência rodada; a posição n+1 é V₀ de novo
+    fn original(&self, pos: usize) -> usize {
+        (self.inicio + pos) % self.vertices()
+    }
+
+    fn peso(&self, pos: usize) -> u128 {
+        self.p[self.original(pos)] as u128
+    }
+
+    // Ordem total dos vértices: pelo peso e, no empate, pela posição. As duas
+    // cópias de V₀ ficam empatadas como o menor vértice.
+    fn chave(&self, pos: usize) -> (usize, usize) {
+        let pos_desempate = if pos == self.vertices() { 0 } else { pos };
+        (self.p[self.original(pos)], pos_desempate)
+    }
+}
+
+fn soma(a: u128, b: u128) -> Result<u128, CostOverflow> {
+    a.checked_add(b).ok_or(CostOverflow::of::<u128>())
+}
+
+fn produto(a: u128, b: u128) -> Result<u128, CostOverflow> {
+    a.checked_mul(b).ok_or(CostOverflow::of::<u128>())
+}
+
+impl Face {
+    fn mantem(&mut self, c: usize, arcos: &[Arco], heaps: &mut Heaps, poligono: &Poligono) -> Result<(), CostOverflow> {
+        let arco = &arcos[c];
+        self.heap = heaps.une(self.heap, Some(c), arcos);
+        self.fixo = soma(self.fixo, arco.custo)?;
+        self.linear = soma(self.linear, produto(poligono.peso(arco.i), poligono.peso(arco.j))?)?;
+        Ok(())
+    }
+
+    // Descarta o arco de maior peso de suporte: ele sai da fronteira e os
+    // arcos mantidos acima dele passam a fazer parte da face
+    fn descarta_topo(&mut self, arcos: &[Arco], heaps: &mut Heaps, poligono: &Poligono) -> Result<(), CostOverflow> {
+        let topo = self.heap.expect("heap não vazio");
+        let arco = &arcos[topo];
+        let restante = heaps.remove_topo(topo, arcos);
+        self.heap = heaps.une(restante, arco.escondido, arcos);
+        self.fixo = soma(self.fixo, arco.fixo)? - arco.custo;
+        self.linear = soma(self.linear, arco.linear)? - produto(poligono.peso(arco.i), poligono.peso(arco.j))?;
+        Ok(())
+    }
+
+    fn topo(&self, arcos: &[Arco]) -> Option<Fracao> {
+        self.heap.map(|h| arcos[h].suporte)
+    }
+}
+
+// Constrói os arcos potenciais (um por nó da árvore cartesiana das posições
+// 1..=n) e devolve também a raiz
+fn arcos_potenciais(poligono: &Poligono) -> (Vec<Arco>, usize) {
+    let n = poligono.vertices() - 1;
+    let mut esquerdo = vec![None; n + 1];
+    let mut direito = vec![None; n + 1];
+    let mut pilha: Vec<usize> = Vec::new();
+    for (pos, esquerdo) in esquerdo.iter_mut().enumerate().skip(1) {
+        let mut ultimo = None;
+        while let Some(&topo) = pilha.last() {
+            if poligono.chave(topo) < poligono.chave(pos) {
+                break;
+            }
+            ultimo = pilha.pop();
+        }
+        *esquerdo = ultimo;
+        if let Some(&topo) = pilha.last() {
+            direito[topo] = Some(pos);
+        }
+        pilha.push(pos);
+    }
+    let raiz = pilha[0];
+
+    // Intervalo (i, j) de cada nó, de cima para baixo
+    let mut arcos: Vec<Arco> = (0..=n)
+        .map(|m| Arco {
+            i: 0,
+            j: 0,
+            esquerdo: esquerdo[m],
+            direito: direito[m],
+            x: 0,
+            custo: 0,
+            suporte: Fracao::inteiro(0),
+            fixo: 0,
+            linear: 0,
+            escondido: None,
+        })
+        .collect();
+    let mut pendentes = vec![(raiz, 0, n + 1)];
+    while let Some((m, i, j)) = pendentes.pop() {
+        arcos[m].i = i;
+        arcos[m].j = j;
+        arcos[m].x = if poligono.chave(i) <= poligono.chave(j) { i } else { j };
+        if let Some(e) = esquerdo[m] {
+            pendentes.push((e, i, m));
+        }
+        if let Some(d) = direito[m] {
+            pendentes.push((d, m, j));
+        }
+    }
+    (arcos, raiz)
+}
+
+// Calcula C(h), T(h) e o heap escondido do arco m, supondo seus filhos prontos
+fn processa(m: usize, arcos: &mut [Arco], heaps: &mut Heaps, poligono: &Poligono) -> Result<(), CostOverflow> {
+    let Arco { i, j, esquerdo, direito, x, .. } = arcos[m];
+    let wx = poligono.peso(x);
+
+    // Lados do triângulo (i, m, j) que são lados do polígono, e quanto deles
+    // não toca x (só esses viram triângulos do leque de x)
+    let mut lados = 0;
+    let mut lados_sem_x = 0;
+    for (u, v, filho) in [(i, m, esquerdo), (m, j, direito)] {
+        if filho.is_none() {
+            let lado = produto(poligono.peso(u), poligono.peso(v))?;
+            lados = soma(lados, lado)?;
+            if u != x && v != x {
+                lados_sem_x = soma(lados_sem_x, lado)?;
+            }
+        }
+    }
+
+    // O filho que toca x continua a mesma face (o leque de x) qualquer que
+    // seja a decisão; o outro é mantido ou descartado pelo peso de suporte
+    let (incidente, outro) = if x == i { (esquerdo, direito) } else { (direito, esquerdo) };
+
+    let mut face = Face { heap: None, fixo: 0, linear: lados };
+    if let Some(c) = outro {
+        face.mantem(c, arcos, heaps, poligono)?;
+    }
+    let wx_fracao = Fracao::inteiro(wx);
+    while face.topo(arcos).is_some_and(|t| t > wx_fracao) {
+        face.descarta_topo(arcos, heaps, poligono)?;
+    }
+    let mut custo = soma(face.fixo, produto(wx, face.linear - (lados - lados_sem_x))?)?;
+    if let Some(c) = incidente {
+        custo = soma(custo, arcos[c].custo)?;
+        face.mantem(c, arcos, heaps, poligono)?;
+    }
+    arcos[m].custo = custo;
+
+    // Abaixo de h o ápice pesa menos que wx. Descendo w, a face acima de h é
+    // H(w) = fixo + w·linear enquanto nenhum arco do heap é descartado, e o
+    // arco h vale a pena quando custo + w·wᵢwⱼ ≤ H(w).
+    let peso_h = produto(poligono.peso(i), poligono.peso(j))?;
+    let suporte = loop {
+        let limite = face.topo(arcos).unwrap_or(Fracao::inteiro(0));
+        // O lado de cima da face sempre pesa mais que o arco, então linear > peso_h
+        let cruzamento = if custo > face.fixo {
+            Fracao { num: custo - face.fixo, den: face.linear - peso_h }
+        } else {
+            Fracao::inteiro(0)
+        };
+        if cruzamento >= limite {
+            break cruzamento;
+        }
+        face.descarta_topo(arcos, heaps, poligono)?;
+    };
+
+    let arco = &mut arcos[m];
+    arco.suporte = suporte;
+    arco.fixo = face.fixo;
+    arco.linear = face.linear;
+    arco.escondido = face.heap;
+    Ok(())
+}
+
+// Lista os triângulos da solução, percorrendo as faces de cima para baixo com
+// as mesmas decisões usadas no cálculo dos custos
+fn triangulos(raiz: usize, arcos: &[Arco], poligono: &Poligono) -> Vec<[usize; 3]> {
+    let mut triangulos = Vec::new();
+    // (arco cuja região pertence à face, ápice da face)
+    let mut pendentes = vec![(raiz, arcos[raiz].x)];
+    while let Some((m, apice)) = pendentes.pop() {
+        let Arco { i, j, esquerdo, direito, .. } = arcos[m];
+        for (u, v, filho) in [(i, m, esquerdo), (m, j, direito)] {
+            // Compara pelos vértices originais: as posições 0 e n+1 são ambas V₀
+            let toca_apice = [u, v].iter().any(|&w| poligono.original(w) == poligono.original(apice));
+            match filho {
+                None => {
+                    if !toca_apice {
+                        triangulos.push([apice, u, v]);
+                    }
+                }
+                Some(c) if toca_apice || arcos[c].suporte > Fracao::inteiro(poligono.peso(apice)) => {
+                    pendentes.push((c, apice));
+                }
+                Some(c) => {
+                    triangulos.push([apice, u, v]);
+                    pendentes.push((c, arcos[c].x));
+                }
+            }
+        }
+    }
+    triangulos
+        .into_iter()
+        .map(|t| t.map(|pos| poligono.original(pos)))
+        .collect()
+}
+
+// Monta a árvore de associação a partir dos triângulos. O lado (u, t) do
+// polígono, com u < t, corresponde ao produto Aᵤ₊₁…Aₜ, e o triângulo sobre
+// ele o divide em Aᵤ₊₁…Aₖ e Aₖ₊₁…Aₜ.
+fn arvore(n: usize, triangulos: &[[usize; 3]]) -> ParenTree {
+    let mut divisao = HashMap::with_capacity(triangulos.len());
+    for triangulo in triangulos {
+        let mut t = *triangulo;
+        t.sort_unstable();
+        divisao.insert((t[0], t[2]), t[1]);
+    }
+    ParenTree::from_split_fn(1, n, |i, j| divisao[&(i - 1, j)])
+}
+
+/// Ordem ótima da cadeia com dimensões `p` pelo algoritmo de Hu–Shing.
+/// Devolve o custo mínimo e a associação correspondente.
+pub fn hu_shing<C: Cost>(p: &[usize]) -> Result<(C, ParenTree), PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    let n = p.len() - 1;
+    if n == 1 {
+        return Ok((C::ZERO, ParenTree::Leaf(1)));
+    }
+
+    let inicio = (0..=n).min_by_key(|&v| (p[v], v)).unwrap();
+    let poligono = Poligono { p, inicio };
+
+    let triangulos = if p[inicio] == 0 {
+        // Todo triângulo que usa o vértice de peso zero custa 0, então o leque
+        // a partir dele é ótimo
+        (1..n).map(|pos| [0, pos, pos + 1].map(|pos| poligono.original(pos))).collect()
+    } else {
+        let (mut arcos, raiz) = arcos_potenciais(&poligono);
+        let mut heaps = Heaps::new(arcos.len());
+
+        // Os filhos têm chave maior que o pai na árvore cartesiana, então
+        // processar por chave decrescente trata os filhos antes dos pais
+        let mut ordem: Vec<usize> = (1..=n).filter(|&m| m != raiz).collect();
+        ordem.sort_unstable_by_key(|&m| std::cmp::Reverse(poligono.chave(m)));
+        for m in ordem {
+            processa(m, &mut arcos, &mut heaps, &poligono)?;
+        }
+        triangulos(raiz, &arcos, &poligono)
+    };
+
+    let arvore = arvore(n, &triangulos);
+    let custo = arvore.cost::<C>(p).ok_or(PlanError::Overflow(CostOverflow::of::<C>()))?;
+    Ok((custo, arvore))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multiplicacao_de_matrizes;
+    use crate::test_support::{CLRS, dimensoes};
+
+    fn confere(p: &[usize]) {
+        let n = p.len() - 1;
+        let (m, _) = multiplicacao_de_matrizes::<u128>(p).unwrap();
+        let (custo, arvore) = hu_shing::<u128>(p).unwrap();
+        assert_eq!(Some(custo), m.get(1, n), "p = {:?}", p);
+        assert_eq!(arvore.range(), (1, n));
+    }
+
+    #[test]
+    fn test_fraction_order() {
+        let f = |num, den| Fracao { num, den };
+        assert!(f(1, 3) < f(1, 2));
+        assert!(f(2, 4) == f(1, 2));
+        assert!(f(7, 3) > f(9, 4));
+        assert!(f(u128::MAX - 1, u128::MAX) > f(u128::MAX - 2, u128::MAX - 1));
+        assert!(f(0, 5) == Fracao::inteiro(0));
+    }
+
+    #[test]
+    fn test_clrs_chain() {
+        let (custo, _) = hu_shing::<u64>(&CLRS).unwrap();
+        assert_eq!(custo, 15125);
+    }
+
+    #[test]
+    fn test_too_few_dimensions() {
+        assert_eq!(hu_shing::<u64>(&[]), Err(PlanError::TooFewDimensions));
+        assert_eq!(hu_shing::<u64>(&[7]), Err(PlanError::TooFewDimensions));
+    }
+
+    #[test]
+    fn test_long_skewed_chain() {
+        // Com dimensões crescentes a ordem ótima é da esquerda para a direita,
+        // uma árvore com n níveis que a recursão não aguentaria
+        let n = 100_000;
+        let p: Vec<usize> = (1..=n + 1).collect();
+        let (custo, arvore) = hu_shing::<u64>(&p).unwrap();
+        let n = n as u64;
+        // Σ 1·k·(k+1) para k de 2 a n
+        assert_eq!(custo, n * (n + 1) * (n + 2) / 3 - 2);
+        assert_eq!(arvore.range(), (1, p.len() - 1));
+        assert_eq!(arvore.cost::<u64>(&p), Some(custo));
+    }
+
+    #[test]
+    fn test_small_chains() {
+        confere(&[10, 20]);
+        confere(&[10, 20, 30]);
+        confere(&[5, 1, 5, 1, 5]);
+        confere(&[1, 5, 1, 5, 1]);
+        confere(&[3, 3, 3, 3, 3, 3]);
+        confere(&[0, 4, 6, 2]);
+        confere(&[4, 6, 0, 2, 9]);
+    }
+
+    #[test]
+    fn test_random_against_dynamic_programming() {
+        let mut estado = 2026;
+        for n in 1..=12 {
+            for maximo in [2, 3, 5, 10, 100, 10_000] {
+                for _ in 0..40 {
+                    confere(&dimensoes(&mut estado, n, maximo));
+                }
+            }
+        }
+        for _ in 0..30 {
+            let n = 13 + (estado as usize) % 100;
+            confere(&dimensoes(&mut estado, n, 50));
+        }
+    }
+}
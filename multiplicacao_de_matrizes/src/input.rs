@@ -0,0 +1,212 @@
+//! Leitura da cadeia de matrizes digitada pelo usuário. Aceita o vetor de
+//! dimensões separado por vírgulas ou espaços (`10, 20, 30` ou `10 20 30`) ou
+//! a notação de pares (`10x20, 20x30`), devolvendo sempre o vetor `p`.
+
+use std::fmt;
+
+use mensagens::Lang;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidNumber,
+    ZeroDimension,
+    // Um par `axb` misturado com dimensões soltas, ou vice-versa
+    MixedNotation,
+    // Em `AxB, CxD` as colunas da primeira (B) devem ser as linhas da segunda (C)
+    DimensionMismatch { expected: usize, found: usize },
+    TooFewDimensions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    // Posição do token problemático: índice (a partir de 1) entre os tokens e
+    // coluna (a partir de 1, em caracteres) na linha digitada
+    pub token_index: usize,
+    pub column: usize,
+    pub token: String,
+}
+
+impl ParseError {
+    pub fn message(&self, lang: Lang) -> String {
+        let motivo = match &self.kind {
+            ParseErrorKind::InvalidNumber => lang.format("entrada.numero_invalido", &[]),
+            ParseErrorKind::ZeroDimension => lang.format("entrada.dimensao_zero", &[]),
+            ParseErrorKind::MixedNotation => lang.format("entrada.notacao_mista", &[]),
+            ParseErrorKind::DimensionMismatch { expected, found } => {
+                lang.format("entrada.dimensoes_incompativeis", &[expected, found])
+            }
+            ParseErrorKind::TooFewDimensions => return lang.format("entrada.poucas_dimensoes", &[]),
+        };
+        lang.format("entrada.erro_no_token", &[&self.token_index, &self.column, &self.token, &motivo])
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Token<'a> {
+    text: &'a str,
+    index: usize,
+    column: usize,
+}
+
+impl Token<'_> {
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            kind,
+            token_index: self.index,
+            column: self.column,
+            token: self.text.to_owned(),
+        }
+    }
+}
+
+// Separa a linha em tokens por vírgulas e espaços, guardando a coluna de cada um
+fn tokens(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (column, (offset, c)) in line.char_indices().enumerate() {
+        let separador = c == ',' || c.is_whitespace();
+        match (start, separador) {
+            (None, false) => start = Some((offset, column)),
+            (Some((inicio, coluna)), true) => {
+                tokens.push(Token { text: &line[inicio..offset], index: tokens.len() + 1, column: coluna + 1 });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((inicio, coluna)) = start {
+        tokens.push(Token { text: &line[inicio..], index: tokens.len() + 1, column: coluna + 1 });
+    }
+    tokens
+}
+
+fn dimension(token: &Token, text: &str) -> Result<usize, ParseError> {
+    match text.parse::<usize>() {
+        Ok(0) => Err(token.error(ParseErrorKind::ZeroDimension)),
+        Ok(d) => Ok(d),
+        Err(_) => Err(token.error(ParseErrorKind::InvalidNumber)),
+    }
+}
+
+fn split_pair(text: &str) -> Option<(&str, &str)> {
+    text.split_once(['x', 'X', '×'])
+}
+
+/// Converte a linha digitada no vetor de dimensões `p` (com pelo menos dois
+/// elementos, todos positivos).
+pub fn parse_dimensions(line: &str) -> Result<Vec<usize>, ParseError> {
+    let tokens = tokens(line);
+    let Some(primeiro) = tokens.first() else {
+        return Err(ParseError {
+            kind: ParseErrorKind::TooFewDimensions,
+            token_index: 0,
+            column: 0,
+            token: String::new(),
+        });
+    };
+    let pares = split_pair(primeiro.text).is_some();
+
+    let mut p = Vec::with_capacity(tokens.len() + 1);
+    for token in &tokens {
+        match (pares, split_pair(token.text)) {
+            (true, Some((linhas, colunas))) => {
+                let linhas = dimension(token, linhas)?;
+                let colunas = dimension(token, colunas)?;
+                match p.last() {
+                    Some(&anterior) if anterior != linhas => {
+                        return Err(token.error(ParseErrorKind::DimensionMismatch { expected: anterior, found: linhas }));
+                    }
+                    Some(_) => {}
+                    None => p.push(linhas),
+                }
+                p.push(colunas);
+            }
+            (false, None) => p.push(dimension(token, token.text)?),
+            _ => return Err(token.error(ParseErrorKind::MixedNotation)),
+        }
+    }
+
+    if p.len() < 2 {
+        return Err(tokens[0].error(ParseErrorKind::TooFewDimensions));
+    }
+    Ok(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commas_and_spaces() {
+        assert_eq!(parse_dimensions("30,35,15\n"), Ok(vec![30, 35, 15]));
+        assert_eq!(parse_dimensions("30, 35 ,15"), Ok(vec![30, 35, 15]));
+        assert_eq!(parse_dimensions("  30 35\t15 "), Ok(vec![30, 35, 15]));
+    }
+
+    #[test]
+    fn test_pair_notation() {
+        assert_eq!(parse_dimensions("10x20, 20x30"), Ok(vec![10, 20, 30]));
+        assert_eq!(parse_dimensions("10×20 20X30 30x5"), Ok(vec![10, 20, 30, 5]));
+        assert_eq!(parse_dimensions("10x20"), Ok(vec![10, 20]));
+    }
+
+    #[test]
+    fn test_invalid_number_position() {
+        let erro = parse_dimensions("30, 35, abc, 15").unwrap_err();
+        assert_eq!(erro.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(erro.token_index, 3);
+        assert_eq!(erro.column, 9);
+        assert_eq!(erro.token, "abc");
+    }
+
+    #[test]
+    fn test_zero_dimension() {
+        let erro = parse_dimensions("30, 0, 15").unwrap_err();
+        assert_eq!(erro.kind, ParseErrorKind::ZeroDimension);
+        assert_eq!(erro.token_index, 2);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let erro = parse_dimensions("10x20, 25x30").unwrap_err();
+        assert_eq!(erro.kind, ParseErrorKind::DimensionMismatch { expected: 20, found: 25 });
+        assert_eq!(erro.token_index, 2);
+        assert_eq!(erro.column, 8);
+    }
+
+    #[test]
+    fn test_mixed_notation() {
+        let erro = parse_dimensions("10x20, 30").unwrap_err();
+        assert_eq!(erro.kind, ParseErrorKind::MixedNotation);
+        assert_eq!(erro.token, "30");
+    }
+
+    #[test]
+    fn test_too_few_dimensions() {
+        assert_eq!(parse_dimensions("").unwrap_err().kind, ParseErrorKind::TooFewDimensions);
+        assert_eq!(parse_dimensions(" ,\n").unwrap_err().kind, ParseErrorKind::TooFewDimensions);
+        assert_eq!(parse_dimensions("10").unwrap_err().kind, ParseErrorKind::TooFewDimensions);
+    }
+
+    #[test]
+    fn test_messages() {
+        let erro = parse_dimensions("10x20, 25x30").unwrap_err();
+        assert_eq!(
+            erro.to_string(),
+            "Erro no token 2 (coluna 8, \"25x30\"): a matriz deveria ter 20 linhas para ser multiplicada pela anterior, mas tem 25"
+        );
+        assert_eq!(
+            erro.message(Lang::En),
+            "Error in token 2 (column 8, \"25x30\"): the matrix should have 20 rows to be multiplied by the previous one, but it has 25"
+        );
+        assert_eq!(parse_dimensions("10").unwrap_err().message(Lang::En), "Enter at least two dimensions (one matrix)");
+    }
+}
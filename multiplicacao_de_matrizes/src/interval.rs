@@ -0,0 +1,366 @@
+//! Programação dinâmica por intervalos genérica.
+//!
+//! Vários problemas têm o mesmo esqueleto da cadeia de matrizes: o melhor
+//! custo de um intervalo i..=j de elementos é o menor, entre as divisões
+//! i..=k e k+1..=j, da soma dos custos das duas metades com o custo de
+//! juntá-las. O motor calcula as tabelas m e s para qualquer problema que
+//! diga o custo de cada intervalo unitário e de cada junção; a cadeia de
+//! matrizes ([`MatrixChain`](crate::MatrixChain)), a árvore de busca binária
+//! ótima ([`bst`](crate::bst)) e a triangulação de polígonos
+//! ([`triangulation`](crate::triangulation)) são instâncias dele.
+
+use std::{fmt, ops::Range};
+
+use crate::{Cost, CostOverflow, TriangularTable};
+
+/// Um problema de programação dinâmica por intervalos sobre os elementos
+/// 1..=n, com custos do tipo `C`.
+///
+/// Como em [`CostModel`](crate::CostModel), cada intervalo pode carregar uma
+/// informação própria (`Info`), calculada sempre da esquerda para a direita,
+/// juntando i..=j-1 com j..=j, para não depender da divisão escolhida.
+pub trait IntervalProblem<C: Cost> {
+    type Info: Copy;
+
+    /// Quantidade de elementos.
+    fn size(&self) -> usize;
+
+    /// Informação do intervalo i..=i.
+    fn info(&self, i: usize) -> Self::Info;
+
+    /// Custo do intervalo i..=i, ou `None` se ele não cabe em `C`.
+    fn base(&self, i: usize) -> Option<C>;
+
+    /// Informação de i..=j a partir da de i..=j-1 (`left`) e da de j..=j (`right`).
+    fn combine(&self, i: usize, j: usize, left: Self::Info, right: Self::Info) -> Self::Info;
+
+    /// Custo de juntar i..=k (com informação `left`) e k+1..=j (com `right`),
+    /// sem contar o custo das duas partes.
+    fn split(&self, i: usize, k: usize, j: usize, left: Self::Info, right: Self::Info) -> Option<C>;
+}
+
+// Célula (i, j) das tabelas internas: o custo mínimo de i..=j (vazio se nenhum
+// cabe em C) junto com a informação do problema sobre esse intervalo
+pub(crate) type Celula<C, I> = (Option<C>, I);
+
+/// Quais divisões k de cada intervalo i..=j o motor experimenta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitSearch {
+    /// Todas, de i até j-1: O(n³) no total.
+    #[default]
+    Full,
+    /// Só as de s[i][j-1] até s[i+1][j] (aceleração de Knuth–Yao), O(n²) no
+    /// total. Só é correta quando o custo de juntar não depende de k e
+    /// satisfaz a desigualdade quadrangular; veja [`check_knuth_conditions`].
+    /// Nesses casos as tabelas saem idênticas às de [`SplitSearch::Full`].
+    Knuth,
+}
+
+/// Calcula as tabelas m (custo mínimo de cada intervalo) e s (onde dividir
+/// cada intervalo) do problema. Divisões cujo custo não cabe em `C` são
+/// descartadas; só é erro se nenhuma couber para o intervalo inteiro.
+pub fn solve<C: Cost, P: IntervalProblem<C>>(problema: &P) -> Result<(TriangularTable<C>, TriangularTable<usize>), CostOverflow> {
+    solve_with(problema, SplitSearch::Full)
+}
+
+/// Mesmo que [`solve`], escolhendo como procurar a melhor divisão.
+pub fn solve_with<C: Cost, P: IntervalProblem<C>>(problema: &P, busca: SplitSearch) -> Result<(TriangularTable<C>, TriangularTable<usize>), CostOverflow> {
+    let n = problema.size();
+    // Cada célula guarda o custo e a informação juntos, para que o laço em k
+    // leia as duas coisas de uma vez. A tabela m é montada no final.
+    let mut celulas = TriangularTable::<Celula<C, P::Info>>::new(n);
+    let mut s = TriangularTable::new(n);
+    // Cópia espelhada das células, com (i, j) guardado em (n+1-j, n+1-i). O
+    // laço em k percorre a linha i e a coluna j; no espelho a coluna vira uma
+    // linha contígua na memória, o que evita uma falta de cache a cada k.
+    let mut espelho = TriangularTable::new(n);
+
+    for i in 1..=n {
+        let celula = (problema.base(i), problema.info(i));
+        celulas.set(i, i, celula);
+        espelho.set(n+1-i, n+1-i, celula);
+    }
+
+    for l in 2..=n { // l é o tamanho do intervalo
+        for i in 1..=n-l+1 { // i vai de 1 até o início do último intervalo de tamanho l
+            let j = i + l - 1;
+            let divisoes = match busca {
+                SplitSearch::Full => i..j,
+                // Pela monotonicidade da divisão ótima. Se algum dos vizinhos
+                // estourou o tipo, não há limite confiável, e se os limites
+                // se cruzam a monotonicidade não vale aqui; nos dois casos a
+                // busca é completa
+                SplitSearch::Knuth if l > 2 => match (s.get(i, j-1), s.get(i+1, j)) {
+                    (Some(inicio), Some(fim)) if inicio <= fim => inicio..fim + 1,
+                    _ => i..j,
+                },
+                SplitSearch::Knuth => i..j,
+            };
+            let (celula, k) = calcula_celula(problema, &celulas, &espelho, i, j, divisoes);
+            if let Some(k) = k {
+                s.set(i, j, k);
+            }
+            celulas.set(i, j, celula);
+            espelho.set(n+1-j, n+1-i, celula);
+        }
+    }
+
+    tabela_de_custos(&celulas).map(|m| (m, s))
+}
+
+// Calcula a célula (i, j) e onde dividir i..=j, experimentando as divisões em
+// `divisoes`, a partir dos intervalos menores, que já precisam estar nas duas
+// tabelas. Fica separado para que a versão paralela faça exatamente as mesmas
+// contas, na mesma ordem.
+#[inline]
+pub(crate) fn calcula_celula<C: Cost, P: IntervalProblem<C>>(
+    problema: &P,
+    celulas: &TriangularTable<Celula<C, P::Info>>,
+    espelho: &TriangularTable<Celula<C, P::Info>>,
+    i: usize,
+    j: usize,
+    divisoes: Range<usize>,
+) -> (Celula<C, P::Info>, Option<usize>) {
+    let n = celulas.n();
+    let mut menor: Option<(C, usize)> = None;
+    for k in divisoes { // k vai do início do intervalo (i) até o penúltimo elemento (j-1), somando o custo das duas partes com o de juntá-las e comparando com o menor custo já computado
+        let (esquerda, info_esquerda) = celulas.get(i, k).unwrap();
+        let (direita, info_direita) = espelho.get(n+1-j, n-k).unwrap(); // célula (k+1, j)
+        let q = esquerda.zip(direita).and_then(|(esquerda, direita)| {
+            problema.split(i, k, j, info_esquerda, info_direita)?
+                .checked_add(esquerda)?
+                .checked_add(direita)
+        });
+        let Some(q) = q else { continue };
+        if menor.is_none_or(|(custo, _)| q < custo) { // Em caso de empate fica o primeiro k
+            menor = Some((q, k));
+        }
+    }
+    let info = problema.combine(i, j, celulas.get(i, j-1).unwrap().1, celulas.get(j, j).unwrap().1);
+    ((menor.map(|(custo, _)| custo), info), menor.map(|(_, k)| k))
+}
+
+// Extrai a tabela m das células; sem custo para o intervalo inteiro é estouro
+pub(crate) fn tabela_de_custos<C: Cost, I: Copy>(celulas: &TriangularTable<Celula<C, I>>) -> Result<TriangularTable<C>, CostOverflow> {
+    let n = celulas.n();
+    let mut m = TriangularTable::new(n);
+    for i in 1..=n {
+        for j in i..=n {
+            if let (Some(custo), _) = celulas.get(i, j).unwrap() {
+                m.set(i, j, custo);
+            }
+        }
+    }
+    if n > 0 && m.get(1, n).is_none() {
+        return Err(CostOverflow::of::<C>());
+    }
+    Ok(m)
+}
+
+/// Motivo pelo qual um problema não pode usar [`SplitSearch::Knuth`]. Os
+/// intervalos são dados pelas pontas, e w(i, j) é o custo de juntar i..=j.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KnuthViolation {
+    /// O custo de juntar i..=j muda conforme a divisão k.
+    DependsOnSplit { i: usize, j: usize },
+    /// w(a, c) + w(a+1, c+1) > w(a, c+1) + w(a+1, c).
+    Quadrangle { a: usize, c: usize },
+    /// w cresce quando o intervalo i..=j encolhe.
+    NotMonotone { i: usize, j: usize },
+}
+
+impl fmt::Display for KnuthViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KnuthViolation::DependsOnSplit { i, j } => {
+                write!(f, "o custo de juntar {i}..={j} depende da divisão")
+            }
+            KnuthViolation::Quadrangle { a, c } => {
+                write!(f, "a desigualdade quadrangular falha em a = {a}, c = {c}")
+            }
+            KnuthViolation::NotMonotone { i, j } => {
+                write!(f, "o custo de juntar {i}..={j} é maior que o de um intervalo que o contém")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KnuthViolation {}
+
+/// Confere as condições que garantem que [`SplitSearch::Knuth`] dá as mesmas
+/// tabelas que a busca completa: o custo w(i, j) de juntar i..=j não depende
+/// da divisão, é monotônico na inclusão de intervalos e satisfaz a
+/// desigualdade quadrangular w(a, c) + w(b, d) ≤ w(a, d) + w(b, c) para
+/// a ≤ b ≤ c ≤ d. Basta conferir as duas últimas entre intervalos vizinhos,
+/// porque o caso geral é soma deles. Custa O(n³), então serve para testes
+/// e depuração, não para toda chamada. Custos que não cabem em `C` contam
+/// como infinitos.
+pub fn check_knuth_conditions<C: Cost, P: IntervalProblem<C>>(problema: &P) -> Result<(), KnuthViolation> {
+    let n = problema.size();
+    let mut infos = TriangularTable::new(n);
+    for i in 1..=n {
+        infos.set(i, i, problema.info(i));
+    }
+    for i in 1..=n {
+        for j in i + 1..=n {
+            let info = problema.combine(i, j, infos.get(i, j - 1).unwrap(), infos.get(j, j).unwrap());
+            infos.set(i, j, info);
+        }
+    }
+
+    let mut w: TriangularTable<Option<C>> = TriangularTable::new(n);
+    for i in 1..=n {
+        for j in i + 1..=n {
+            let custos: Vec<Option<C>> = (i..j)
+                .map(|k| problema.split(i, k, j, infos.get(i, k).unwrap(), infos.get(k + 1, j).unwrap()))
+                .collect();
+            if custos.iter().any(|&custo| custo != custos[0]) {
+                return Err(KnuthViolation::DependsOnSplit { i, j });
+            }
+            w.set(i, j, custos[0]);
+        }
+    }
+
+    // None é infinito: fica depois de todos os valores
+    let maior = |a: Option<C>, b: Option<C>| match (a, b) {
+        (Some(a), Some(b)) => a > b,
+        (None, Some(_)) => true,
+        _ => false,
+    };
+    let soma = |a: Option<C>, b: Option<C>| a.zip(b).and_then(|(a, b)| a.checked_add(b));
+    let peso = |i: usize, j: usize| w.get(i, j).unwrap();
+
+    // Um intervalo unitário não tem junção, então w(c, c) não aparece na
+    // recorrência e pode valer o que for preciso. Os casos que o envolvem
+    // pedem max(w(c-1, c), w(c, c+1)) ≤ w(c-1, c+1), que a monotonicidade já
+    // garante, e por isso ficam de fora.
+    for i in 1..=n {
+        for j in i + 2..=n {
+            if maior(peso(i + 1, j), peso(i, j)) || maior(peso(i, j - 1), peso(i, j)) {
+                return Err(KnuthViolation::NotMonotone { i, j });
+            }
+        }
+    }
+    for a in 1..n {
+        for c in a + 2..n {
+            let esquerda = soma(peso(a, c), peso(a + 1, c + 1));
+            let direita = soma(peso(a, c + 1), peso(a + 1, c));
+            if maior(esquerda, direita) {
+                return Err(KnuthViolation::Quadrangle { a, c });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{CLRS, proximo};
+
+    // Intercalação ótima de arquivos em sequência: juntar dois blocos
+    // vizinhos custa a soma dos tamanhos de todos os arquivos envolvidos
+    struct Intercalacao(Vec<u64>);
+
+    impl IntervalProblem<u64> for Intercalacao {
+        type Info = u64; // Tamanho total do intervalo
+
+        fn size(&self) -> usize {
+            self.0.len()
+        }
+
+        fn info(&self, i: usize) -> u64 {
+            self.0[i - 1]
+        }
+
+        fn base(&self, _: usize) -> Option<u64> {
+            Some(0)
+        }
+
+        fn combine(&self, _: usize, _: usize, left: u64, right: u64) -> u64 {
+            left + right
+        }
+
+        fn split(&self, _: usize, _: usize, _: usize, left: u64, right: u64) -> Option<u64> {
+            Some(left + right)
+        }
+    }
+
+    #[test]
+    fn test_custom_problem() {
+        // (1 + 2) = 3, depois 3 + 3 = 6, depois (6 + 4) = 10: total 19
+        let (m, s) = solve(&Intercalacao(vec![1, 2, 3, 4])).unwrap();
+        assert_eq!(m.get(1, 4), Some(19));
+        assert_eq!(s.get(1, 4), Some(3));
+        assert_eq!(s.get(1, 3), Some(2));
+    }
+
+    #[test]
+    fn test_knuth_matches_full_search() {
+        let mut estado: u64 = 0x0123_4567_89AB_CDEF;
+        for n in 1..=40 {
+            let tamanhos: Vec<u64> = (0..n).map(|_| proximo(&mut estado) % 10).collect();
+            let problema = Intercalacao(tamanhos);
+            assert_eq!(check_knuth_conditions(&problema), Ok(()));
+            assert_eq!(solve_with(&problema, SplitSearch::Knuth), solve(&problema));
+        }
+    }
+
+    // Junções de custo 1, exceto as listadas. Em 2..=4 nenhuma cabe no tipo,
+    // então 1..=4 e 2..=5 são buscados por completo e escolhem 3 e 2, e os
+    // limites de Knuth para 1..=5 se cruzam
+    struct Cruzada;
+
+    impl IntervalProblem<u64> for Cruzada {
+        type Info = ();
+
+        fn size(&self) -> usize {
+            5
+        }
+
+        fn info(&self, _: usize) {}
+
+        fn base(&self, _: usize) -> Option<u64> {
+            Some(0)
+        }
+
+        fn combine(&self, _: usize, _: usize, _: (), _: ()) {}
+
+        fn split(&self, i: usize, k: usize, j: usize, _: (), _: ()) -> Option<u64> {
+            match (i, k, j) {
+                (2, _, 4) => None,
+                (1, 3, 4) | (2, 2, 5) => Some(0),
+                (1, _, 4) | (2, _, 5) => Some(10),
+                _ => Some(1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_knuth_falls_back_when_bounds_cross() {
+        let (_, s) = solve_with(&Cruzada, SplitSearch::Knuth).unwrap();
+        assert_eq!((s.get(1, 4), s.get(2, 5)), (Some(3), Some(2)));
+        assert_eq!(solve_with(&Cruzada, SplitSearch::Knuth), solve(&Cruzada));
+    }
+
+    #[test]
+    fn test_matrix_chain_is_not_knuth() {
+        use crate::{MatrixChain, Naive};
+        let cadeia = MatrixChain { p: &CLRS, model: &Naive };
+        assert_eq!(check_knuth_conditions::<u64, _>(&cadeia), Err(KnuthViolation::DependsOnSplit { i: 1, j: 3 }));
+    }
+
+    #[test]
+    fn test_quadrangle_violation() {
+        use crate::triangulation::Triangulation;
+        // Peso que só depende do tamanho do polígono, mas é côncavo nele
+        let problema = Triangulation { vertices: 6, weight: |a: usize, _, c: usize| Some((c - a).min(3) as u64) };
+        assert_eq!(check_knuth_conditions(&problema), Err(KnuthViolation::Quadrangle { a: 1, c: 3 }));
+    }
+
+    #[test]
+    fn test_empty_problem() {
+        let (m, s) = solve(&Intercalacao(Vec::new())).unwrap();
+        assert_eq!((m.n(), s.n()), (0, 0));
+    }
+}
@@ -0,0 +1,226 @@
+pub mod batch;
+pub mod bst;
+pub mod contraction;
+pub mod cost;
+pub mod expr;
+pub mod format;
+pub mod hu_shing;
+pub mod input;
+pub mod interval;
+pub mod matrix;
+pub mod memo;
+pub mod model;
+pub mod named;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod plan;
+pub mod table;
+#[cfg(test)]
+mod test_support;
+pub mod tree;
+pub mod triangulation;
+
+pub use contraction::{ContractionError, ContractionPath, Einsum, PathStrategy, contraction_path};
+pub use cost::{Cost, CostOverflow};
+pub use expr::{CostOfError, ExprError, cost_of};
+pub use format::format_index;
+pub use input::{ParseError, parse_dimensions};
+pub use interval::IntervalProblem;
+pub use matrix::{Matrix, multiply_chain};
+pub use memo::multiplicacao_de_matrizes_memoizada;
+pub use mensagens::Lang;
+pub use model::{CostModel, MemoryTraffic, Naive, Sparse, Strassen};
+pub use named::{NamedChain, NamedError, parse_bindings};
+pub use plan::{ChainPlan, MemoryPlan, PlanError, pareto_plans, plan, plan_within};
+pub use table::TriangularTable;
+pub use tree::{Notation, ParenTree};
+
+/// Algoritmo usado por [`optimal_order`] para escolher a associação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Programação dinâmica O(n³) de `multiplicacao_de_matrizes`.
+    #[default]
+    Dynamic,
+    /// Particionamento de polígonos de Hu–Shing, O(n log n).
+    HuShing,
+}
+
+/// Custo mínimo e associação ótima da cadeia com dimensões `p`, calculados
+/// pela estratégia escolhida. As duas estratégias sempre chegam ao mesmo
+/// custo, mas podem escolher associações diferentes quando há empate.
+pub fn optimal_order<C: Cost>(p: &[usize], strategy: Strategy) -> Result<(C, ParenTree), PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    match strategy {
+        Strategy::Dynamic => {
+            let n = p.len() - 1;
+            let (m, s) = multiplicacao_de_matrizes::<C>(p)?;
+            Ok((m.get(1, n).unwrap(), ParenTree::from_splits(&s, 1, n)))
+        }
+        Strategy::HuShing => hu_shing::hu_shing(p),
+    }
+}
+
+// Calcula as tabelas m (custo mínimo de cada associação) e s (onde dividir
+// cada associação). Candidatos cujo custo não cabe em C são descartados, então
+// m[i][j] fica vazio quando nenhuma forma de multiplicar Aᵢ…Aⱼ cabe em C; isso
+// só vira erro se acontecer com a cadeia inteira. Sem pelo menos duas
+// dimensões (uma matriz) o erro é `PlanError::TooFewDimensions`.
+pub fn multiplicacao_de_matrizes<C: Cost>(p: &[usize]) -> Result<(TriangularTable<C>, TriangularTable<usize>), PlanError> {
+    multiplicacao_de_matrizes_com(p, &Naive)
+}
+
+/// Mesmo cálculo de [`multiplicacao_de_matrizes`], mas com o custo de cada
+/// produto dado por `modelo` em vez da contagem ingênua de multiplicações.
+pub fn multiplicacao_de_matrizes_com<C: Cost, M: CostModel>(p: &[usize], modelo: &M) -> Result<(TriangularTable<C>, TriangularTable<usize>), PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    Ok(interval::solve(&MatrixChain { p, model: modelo })?)
+}
+
+/// A cadeia de matrizes como problema de [`interval`]: os elementos são as
+/// matrizes, e juntar Aᵢ…Aₖ com Aₖ₊₁…Aⱼ custa o produto de uma matriz
+/// p[i-1]×p[k] por uma p[k]×p[j] no modelo escolhido.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixChain<'a, M> {
+    pub p: &'a [usize],
+    pub model: &'a M,
+}
+
+impl<C: Cost, M: CostModel> IntervalProblem<C> for MatrixChain<'_, M> {
+    type Info = M::Operand;
+
+    fn size(&self) -> usize { // Sem dimensões a cadeia é vazia
+        self.p.len().saturating_sub(1)
+    }
+
+    fn info(&self, i: usize) -> M::Operand {
+        self.model.operand(i)
+    }
+
+    fn base(&self, _: usize) -> Option<C> { // Uma matriz sozinha não custa nada
+        Some(C::ZERO)
+    }
+
+    fn combine(&self, i: usize, j: usize, left: M::Operand, right: M::Operand) -> M::Operand {
+        self.model.combine(self.p[i-1], self.p[j-1], self.p[j], left, right)
+    }
+
+    fn split(&self, i: usize, k: usize, j: usize, left: M::Operand, right: M::Operand) -> Option<C> {
+        self.model.product(self.p[i-1], self.p[k], self.p[j], left, right)
+    }
+}
+
+pub fn encontra_associacoes(saida: &mut [String], s: &TriangularTable<usize>, l:usize, r: usize) {
+    if r - l >= 2 {
+        let sep_i = s.get(l, r).unwrap();
+
+        if r - sep_i > 1 && sep_i > l { // Os dois lados têm mais de uma matriz
+            saida[2*sep_i] = ")".to_owned() + &saida[2*sep_i] + "(";
+            saida[2*(l-1)] += "(";
+            saida[2*r] = ")".to_owned() + &saida[2*r];
+        } else if  r - sep_i > 1 {
+            saida[2*sep_i] += "(";
+            saida[2*r] = ")".to_owned() + &saida[2*r];
+        } else if sep_i > l {
+            saida[2*(l-1)] += "(";
+            saida[2*sep_i] = ")".to_owned() + &saida[2*sep_i];
+        }
+        encontra_associacoes(saida, s, l, sep_i);
+        encontra_associacoes(saida, s, sep_i+1, r);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{CLRS, ESTOURA_U64, dimensoes, proximo};
+
+    #[test]
+    fn test_clrs_chain() {
+        let (m, s) = multiplicacao_de_matrizes::<usize>(&CLRS).unwrap();
+        assert_eq!(m.get(1, 6), Some(15125));
+        assert_eq!(s.get(1, 6), Some(3));
+    }
+
+    #[test]
+    fn test_zero_cost_is_not_uncomputed() {
+        // Com p[1] = 0 a associação (A₁A₂)A₃ custa 0, que é um custo real
+        let p = vec![5, 0, 7, 3];
+        let (m, s) = multiplicacao_de_matrizes::<usize>(&p).unwrap();
+        assert_eq!(m.get(1, 3), Some(0));
+        assert_eq!(s.get(1, 3), Some(1));
+    }
+
+    #[test]
+    fn test_overflowing_subchain_is_skipped() {
+        // m[2][3] = 2⁶⁶ não cabe em u64, mas a cadeia toda custa 2·2⁴⁴ + 2²²
+        let b = ESTOURA_U64;
+        let p = vec![1, b, b, b, 1];
+        let (m, _) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+        assert_eq!(m.get(2, 3), None);
+        assert_eq!(m.get(1, 4), Some(2 * (1 << 44) + (1 << 22)));
+    }
+
+    #[test]
+    fn test_dense_models_without_effect_match_naive() {
+        let p = CLRS;
+        let ingenuo = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+        // Limiar acima de todas as dimensões: Strassen nunca entra
+        assert_eq!(multiplicacao_de_matrizes_com::<u64, _>(&p, &Strassen::new(35)).unwrap(), ingenuo);
+        // Densidade 1 em todas as matrizes é o caso denso
+        assert_eq!(multiplicacao_de_matrizes_com::<u64, _>(&p, &Sparse::new(vec![1.0; 6])).unwrap(), ingenuo);
+    }
+
+    #[test]
+    fn test_sparse_model_changes_order() {
+        // Denso, (A₁A₂)A₃ custa 10·100·10 + 10·10·100 = 20000 e A₁(A₂A₃)
+        // custa 100·10·100 + 10·100·100 = 200000. Com A₁ densa e A₂ e A₃
+        // quase vazias, multiplicar A₂A₃ primeiro passa a ser mais barato.
+        let p = vec![10, 100, 10, 100];
+        let (_, s) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+        assert_eq!(s.get(1, 3), Some(2));
+        let modelo = Sparse::new(vec![1.0, 0.001, 0.001]);
+        let (m, s) = multiplicacao_de_matrizes_com::<u64, _>(&p, &modelo).unwrap();
+        assert_eq!(s.get(1, 3), Some(1));
+        // A₂A₃: ⌈100·10·100·10⁻⁶⌉ = 1, com densidade 1 - (1 - 10⁻⁶)¹⁰ < 10⁻⁵;
+        // depois A₁·(A₂A₃): ⌈10·100·100·1·10⁻⁵⌉ = 1. Já (A₁A₂) sozinho custa 100.
+        assert_eq!(m.get(1, 3), Some(2));
+    }
+
+    #[test]
+    fn test_strategies_agree() {
+        let mut estado: u64 = 0x2545_F491_4F6C_DD1D;
+        for _ in 0..500 {
+            let n = (proximo(&mut estado) % 12 + 1) as usize;
+            let p = dimensoes(&mut estado, n, 30);
+            let (custo_dp, arvore_dp) = optimal_order::<u64>(&p, Strategy::Dynamic).unwrap();
+            let (custo_hs, arvore_hs) = optimal_order::<u64>(&p, Strategy::HuShing).unwrap();
+            assert_eq!(custo_dp, custo_hs, "p = {p:?}");
+            assert_eq!(arvore_dp.cost::<u64>(&p), Some(custo_dp));
+            assert_eq!(arvore_hs.cost::<u64>(&p), Some(custo_hs));
+        }
+    }
+
+    #[test]
+    fn test_too_few_dimensions() {
+        for p in [&[][..], &[7]] {
+            assert_eq!(multiplicacao_de_matrizes::<u64>(p), Err(PlanError::TooFewDimensions));
+            assert_eq!(multiplicacao_de_matrizes_memoizada::<u64>(p), Err(PlanError::TooFewDimensions));
+            assert_eq!(optimal_order::<u64>(p, Strategy::HuShing), Err(PlanError::TooFewDimensions));
+        }
+    }
+
+    #[test]
+    fn test_cost_overflow() {
+        let b = ESTOURA_U64;
+        let p = vec![b, b, b, b];
+        assert_eq!(multiplicacao_de_matrizes::<u64>(&p), Err(PlanError::Overflow(CostOverflow::of::<u64>())));
+
+        // Com u128 o mesmo cálculo cabe
+        let (m, _) = multiplicacao_de_matrizes::<u128>(&p).unwrap();
+        assert_eq!(m.get(1, 3), Some(2 * (1u128 << 66)));
+    }
+}
@@ -1,113 +1,237 @@
-use std::{collections::HashMap, vec, io};
+use std::io::{self, IsTerminal, Read};
+use std::{env, process};
 
-type Matrix = HashMap<usize, HashMap<usize, usize>>;
+use multiplicacao_de_matrizes::batch::{read_chains, result_json};
+use multiplicacao_de_matrizes::{Lang, NamedChain, NamedError, Notation, ParenTree, cost_of, multiplicacao_de_matrizes, pareto_plans, parse_bindings, parse_dimensions, plan, plan_within};
 
-fn multiplicacao_de_matrizes(p: &Vec<usize>) -> (Matrix, Matrix) {
-    let n = p.len() - 1;
-    let mut m: Matrix = HashMap::new();
-    let mut s: Matrix = HashMap::new();
-
-    for l in 2..=n { // l é a quantidade de matrizes que multiplicamos em uma associação
-        for i in 1..=n-l+1 { // i vai de 1 até a posição inicial da última associação de tamanho l possível
-            let j = i + l - 1;
-            for k in i..=j { // k vai da posição inicial da associação (i) até a final(i+l-1), somando o "peso" das subassociações que formam a associação atual e comparando com o menor peso já computado dessa associação
-                let q = m.get(&i)
-                    .and_then(|x| x.get(&k))
-                    .unwrap_or(&0)
-                    + m.get(&(k+1))
-                    .and_then(|x| x.get(&j))
-                    .unwrap_or(&0)
-                    + p[i-1] * p[k] * p[j];
-                let &menor = m.get(&i)
-                    .and_then(|x| x.get(&j))
-                    .unwrap_or(&0);
-                if q < menor || menor == 0 { // Se q for menor do que o menor peso dessa associação já computado, q é o menor peso dessa associação até a atual iteração
-                    m.entry(i)
-                        .or_insert_with(HashMap::new)
-                        .insert(j, q);
-                    s.entry(i)
-                        .or_insert_with(HashMap::new)
-                        .insert(j, k);
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FormatoTabelas {
+    Grade,
+    Csv,
+}
+
+struct Opcoes {
+    tabelas: Option<FormatoTabelas>,
+    todas: bool,
+    expressao: Option<String>,
+    lote: bool,
+    // Teto de memória, em elementos, para cada produto calculado
+    teto: Option<u128>,
+    pareto: bool,
+    idioma: Lang,
+}
+
+fn le_opcoes() -> Opcoes {
+    let (idioma, argumentos) = match Lang::select(env::args().skip(1)) {
+        Ok(selecao) => selecao,
+        Err(erro) => {
+            eprintln!("{erro}");
+            process::exit(2);
+        }
+    };
+    let mut opcoes = Opcoes { tabelas: None, todas: false, expressao: None, lote: false, teto: None, pareto: false, idioma };
+    for argumento in argumentos {
+        match argumento.as_str() {
+            "--tables" | "--tables=grade" => opcoes.tabelas = Some(FormatoTabelas::Grade),
+            "--tables=csv" => opcoes.tabelas = Some(FormatoTabelas::Csv),
+            "--all" => opcoes.todas = true,
+            "--batch" => opcoes.lote = true,
+            "--pareto" => opcoes.pareto = true,
+            _ if argumento.starts_with("--max-memory=") => {
+                let valor = &argumento["--max-memory=".len()..];
+                match valor.parse::<u128>() {
+                    Ok(teto) => opcoes.teto = Some(teto),
+                    Err(_) => {
+                        eprintln!("{}", idioma.format("matrizes.teto_invalido", &[&valor]));
+                        process::exit(2);
+                    }
                 }
             }
+            _ if argumento.starts_with("--cost-of=") => {
+                opcoes.expressao = Some(argumento["--cost-of=".len()..].to_owned());
+            }
+            _ => {
+                eprintln!("{}", idioma.format("matrizes.opcao_desconhecida", &[&argumento]));
+                eprintln!("{}", idioma.text("matrizes.uso"));
+                process::exit(2);
+            }
+        }
+    }
+    // O modo --batch só escreve o resultado de cada cadeia (e as tabelas)
+    if opcoes.lote {
+        let incompativeis = [
+            (opcoes.todas, "--all"),
+            (opcoes.expressao.is_some(), "--cost-of"),
+            (opcoes.teto.is_some(), "--max-memory"),
+            (opcoes.pareto, "--pareto"),
+        ];
+        if let Some((_, opcao)) = incompativeis.iter().find(|(usada, _)| *usada) {
+            eprintln!("{}", idioma.format("matrizes.opcao_incompativel_com_lote", &[opcao]));
+            process::exit(2);
         }
     }
-    (m, s)
+    opcoes
 }
 
-fn encontra_associacoes(saida: &mut [String], s: &Matrix, l:usize, r: usize) {
-    if r - l >= 2 {
-        let &sep_i = s.get(&l)
-            .and_then(|x| x.get(&r))
-            .unwrap();
-
-        if r - sep_i > 1 && sep_i - l > 1 { 
-            saida[2*sep_i] = ")".to_owned() + &saida[2*sep_i] + "(";
-            saida[2*(l-1)] += "(";
-            saida[2*r] = ")".to_owned() + &saida[2*r];
-        } else if  r - sep_i > 1 {
-            saida[2*sep_i] += "(";
-            saida[2*r] = ")".to_owned() + &saida[2*r];
-        } else if sep_i > l {
-            saida[2*(l-1)] += "(";
-            saida[2*sep_i] = ")".to_owned() + &saida[2*sep_i];
-        }
-        encontra_associacoes(saida, s, l, sep_i);
-        encontra_associacoes(saida, s, sep_i+1, r);
+// Uma linha de JSON para cada cadeia da entrada
+fn modo_lote(opcoes: &Opcoes) {
+    let mut entrada = String::new();
+    if io::stdin().read_to_string(&mut entrada).is_err() {
+        eprintln!("{}", opcoes.idioma.text("comum.falha_leitura_entrada"));
+        process::exit(1);
+    }
+    for item in read_chains(&entrada, opcoes.idioma) {
+        println!("{}", result_json(&item, opcoes.tabelas.is_some(), opcoes.idioma));
     }
 }
 
-fn format_index(x: usize) -> String {
-    let subscritos: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
-    x.to_string()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as usize)
-        .map(|d| subscritos[d])
-        .collect()
+// `X: n x d, W: d x 5; n=100, d=20`: as matrizes antes do `;` e os valores
+// dos símbolos depois
+fn le_cadeia_nomeada(linha: &str) -> Result<(Vec<usize>, Vec<String>), NamedError> {
+    let (cadeia, valores) = linha.split_once(';').unwrap_or((linha, ""));
+    let cadeia = NamedChain::parse(cadeia)?;
+    let p = cadeia.dimensions(&parse_bindings(valores)?)?;
+    Ok((p, cadeia.names().into_iter().map(str::to_owned).collect()))
 }
 
 fn main() {
-    println!("Digite as dimensões das matrizes (separe por vírgulas)");
-    let mut p = String::new();
+    let opcoes = le_opcoes();
+    let idioma = opcoes.idioma;
+    if opcoes.lote {
+        modo_lote(&opcoes);
+        return;
+    }
 
-    io::stdin()
-        .read_line(&mut p)
-        .expect("Falha ao ler a linha");
+    // Numa entrada redirecionada ninguém lê o convite
+    if io::stdin().is_terminal() {
+        println!("{}", idioma.text("matrizes.convite_dimensoes"));
+        println!("{}", idioma.text("matrizes.convite_nomes"));
+    }
+    let mut linha = String::new();
 
-    let p:Vec<usize> = p.split(",")
-        .map(|s| s.trim()) // Remove espaços e \n
-        .map(|s| s.parse::<usize>()
-        .expect("Valor inválido, digite números inteiros"))
-        .collect();
+    if io::stdin().read_line(&mut linha).is_err() {
+        eprintln!("{}", idioma.text("comum.falha_leitura_linha"));
+        process::exit(1);
+    }
 
-    let (m,s) = multiplicacao_de_matrizes(&p);
+    let (p, nomes) = if linha.contains(':') {
+        match le_cadeia_nomeada(&linha) {
+            Ok((p, nomes)) => (p, Some(nomes)),
+            Err(erro) => {
+                eprintln!("{}", erro.message(idioma));
+                process::exit(1);
+            }
+        }
+    } else {
+        match parse_dimensions(&linha) {
+            Ok(p) => (p, None),
+            Err(erro) => {
+                eprintln!("{}", erro.message(idioma));
+                if erro.column > 0 { // Aponta para o token problemático na linha digitada
+                    eprintln!("{}", linha.trim_end());
+                    eprintln!("{}^", " ".repeat(erro.column - 1));
+                }
+                process::exit(1);
+            }
+        }
+    };
+    // Sem UTF-8 no terminal, `A_10x20 * A_20x5` no lugar de `A₁₀ₓ₂₀∙A₂₀ₓ₅`
+    let notacao = Notation::auto();
+    // Com nomes, a expressão usa os nomes no lugar das dimensões
+    let desenha = |arvore: &ParenTree| match &nomes {
+        Some(nomes) => arvore.render_named_in(nomes, notacao),
+        None => arvore.render(&p, notacao),
+    };
 
-    let n = p.len() - 1;
-    let mut saida: Vec<String> = vec!(String::from(""));
+    let plano = match plan(&p) {
+        Ok(plano) => plano,
+        Err(erro) => {
+            eprintln!("{}", erro.message(idioma));
+            process::exit(1);
+        }
+    };
 
-    for i in 0..n {
-        saida.push(format!("A{}ₓ{}", format_index(p[i]), format_index(p[i+1])));
-        if i != n-1 { saida.push(String::from("∙")) } else { saida.push(String::from(" ")) }
+    match opcoes.teto {
+        None => {
+            println!("{}", idioma.format("matrizes.custo_minimo", &[&plano.cost]));
+            println!("{}", idioma.text("matrizes.expressao"));
+            println!("{}", desenha(&plano.tree));
+        }
+        // Com teto, a resposta principal é a ordem mais barata que cabe nele
+        Some(teto) => match plan_within(&p, teto) {
+            Ok(limitado) => {
+                println!("{}", idioma.format("matrizes.custo_com_teto", &[&teto, &limitado.cost]));
+                println!("{}", idioma.text("matrizes.expressao"));
+                println!("{}", desenha(&limitado.tree));
+                println!("{}", idioma.format("matrizes.pico", &[&limitado.peak]));
+            }
+            Err(erro) => {
+                eprintln!("{}", erro.message(idioma));
+                process::exit(1);
+            }
+        },
     }
 
-    encontra_associacoes(&mut saida, &s, 1, n);
-    
-    println!("A menor quantidade de multiplicações possível é {}.", m.get(&1).and_then(|x| x.get(&n)).unwrap());
-    println!("Ela ocorre quando multiplicamos as matrizes de acordo com a expressão a seguir:");
-    for termo in &saida {
-        print!("{termo}");
+    if opcoes.pareto {
+        println!();
+        println!("{}", idioma.text("matrizes.fronteira"));
+        // O plano ótimo já coube em u128, então a fronteira também cabe
+        for ponto in pareto_plans(&p).expect("o plano já coube em u128") {
+            println!("{}", idioma.format("matrizes.ponto_da_fronteira", &[&ponto.cost, &ponto.peak, &desenha(&ponto.tree)]));
+        }
     }
-    println!();
 
-    /*for (i, inner_map) in &m {
-        for (j, value) in inner_map {
-            println!("m[{}][{}] = {} ", i, j, value);
+    if let Some(expressao) = &opcoes.expressao {
+        println!();
+        match cost_of::<u128>(&p, expressao) {
+            Ok(custo) => {
+                let diferenca = custo - plano.cost;
+                println!("{}", idioma.format("matrizes.custo_da_associacao", &[expressao, &custo, &diferenca]));
+            }
+            Err(erro) => {
+                eprintln!("{}", erro.message(idioma));
+                process::exit(1);
+            }
         }
     }
-    println!();
-    for (i, inner_map) in &s {
-        for (j, value) in inner_map {
-            println!("s[{}][{}] = {} ", i, j, value);
+
+    if !opcoes.todas && opcoes.tabelas.is_none() {
+        return;
+    }
+    // As opções abaixo precisam das tabelas completas, que o plano não guarda
+    let n = p.len() - 1;
+    let (m, s) = multiplicacao_de_matrizes::<u128>(&p).expect("o plano já coube em u128");
+
+    if opcoes.todas {
+        let todas = ParenTree::all_optimal(&p, &m, 1, n);
+        println!();
+        if todas.len() == 1 {
+            println!("{}", idioma.text("matrizes.unica_associacao"));
+        } else {
+            println!("{}", idioma.format("matrizes.todas_associacoes", &[&todas.len()]));
+            for arvore in todas {
+                println!("{}", desenha(&arvore));
+            }
         }
-    }*/
-}
\ No newline at end of file
+    }
+
+    match opcoes.tabelas {
+        Some(FormatoTabelas::Grade) => {
+            println!();
+            println!("m[i][j]:");
+            print!("{}", m.to_grid());
+            println!();
+            println!("s[i][j]:");
+            print!("{}", s.to_grid());
+        }
+        Some(FormatoTabelas::Csv) => {
+            println!();
+            println!("m");
+            print!("{}", m.to_csv());
+            println!();
+            println!("s");
+            print!("{}", s.to_csv());
+        }
+        None => {}
+    }
+}
@@ -0,0 +1,220 @@
+//! Matrizes densas e a execução de um produto em cadeia na ordem ótima.
+
+use std::{fmt, ops::{Add, Index, Mul}};
+
+use mensagens::Lang;
+
+use crate::{ParenTree, TriangularTable};
+
+/// Matriz densa guardada linha a linha.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "uma matriz {rows}x{cols} precisa de {} elementos", rows * cols);
+        Matrix { rows, cols, data }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|x| f(x / cols, x % cols)).collect();
+        Matrix { rows, cols, data }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Produto ingênuo `self · other`, que faz `rows · cols · other.cols`
+    /// multiplicações escalares. `None` se as dimensões não são compatíveis.
+    pub fn multiply(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        self.multiplica(other, &mut 0)
+    }
+
+    // O produto, somando a `contador` cada multiplicação escalar feita
+    fn multiplica(&self, other: &Matrix<T>, contador: &mut u128) -> Option<Matrix<T>> {
+        if self.cols != other.rows {
+            return None;
+        }
+        let mut data = vec![T::default(); self.rows * other.cols];
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    data[i * other.cols + j] = data[i * other.cols + j] + a * other[(k, j)];
+                    *contador += 1;
+                }
+            }
+        }
+        Some(Matrix { rows: self.rows, cols: other.cols, data })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols, "posição ({i}, {j}) fora de uma matriz {}x{}", self.rows, self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutionError {
+    EmptyChain,
+    // A tabela `s` foi calculada para uma cadeia de outro tamanho
+    TableSize { expected: usize, found: usize },
+    // Aᵢ tem `left_cols` colunas, mas Aᵢ₊₁ tem `right_rows` linhas (i a partir de 1)
+    DimensionMismatch { position: usize, left_cols: usize, right_rows: usize },
+}
+
+impl ExecutionError {
+    pub fn message(&self, lang: Lang) -> String {
+        match self {
+            ExecutionError::EmptyChain => lang.format("execucao.cadeia_vazia", &[]),
+            ExecutionError::TableSize { expected, found } => {
+                lang.format("execucao.tamanho_da_tabela", &[expected, found])
+            }
+            ExecutionError::DimensionMismatch { position, left_cols, right_rows } => lang.format(
+                "execucao.dimensoes_incompativeis",
+                &[position, left_cols, &(position + 1), right_rows],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for ExecutionError {}
+
+/// O produto da cadeia e quantas multiplicações escalares foram feitas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution<T> {
+    pub result: Matrix<T>,
+    pub scalar_multiplications: u128,
+}
+
+/// Multiplica `matrices` na ordem dada pela tabela de divisões `s`.
+pub fn multiply_chain<T>(s: &TriangularTable<usize>, matrices: &[Matrix<T>]) -> Result<Execution<T>, ExecutionError>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    if matrices.is_empty() {
+        return Err(ExecutionError::EmptyChain);
+    }
+    if s.n() != matrices.len() {
+        return Err(ExecutionError::TableSize { expected: s.n(), found: matrices.len() });
+    }
+    for (i, par) in matrices.windows(2).enumerate() {
+        if par[0].cols != par[1].rows {
+            return Err(ExecutionError::DimensionMismatch {
+                position: i + 1,
+                left_cols: par[0].cols,
+                right_rows: par[1].rows,
+            });
+        }
+    }
+
+    let mut scalar_multiplications = 0;
+    let result = evaluate(&ParenTree::from_splits(s, 1, matrices.len()), matrices, &mut scalar_multiplications);
+    Ok(Execution { result, scalar_multiplications })
+}
+
+fn evaluate<T>(arvore: &ParenTree, matrices: &[Matrix<T>], contador: &mut u128) -> Matrix<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    match arvore {
+        ParenTree::Leaf(i) => matrices[i - 1].clone(),
+        ParenTree::Product(esquerda, direita) => {
+            let a = evaluate(esquerda, matrices, contador);
+            let b = evaluate(direita, matrices, contador);
+            a.multiplica(&b, contador).expect("as dimensões foram verificadas antes")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multiplicacao_de_matrizes;
+    use crate::test_support::CLRS;
+
+    fn chain(p: &[usize]) -> Vec<Matrix<i64>> {
+        p.windows(2)
+            .enumerate()
+            .map(|(x, d)| Matrix::from_fn(d[0], d[1], |i, j| ((x + 1) * (i + 2 * j) % 7) as i64 - 3))
+            .collect()
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let b = Matrix::new(3, 2, vec![7, 8, 9, 10, 11, 12]);
+        assert_eq!(a.multiply(&b), Some(Matrix::new(2, 2, vec![58, 64, 139, 154])));
+        assert_eq!(a.multiply(&a), None);
+    }
+
+    #[test]
+    fn test_optimal_order_matches_left_to_right() {
+        let p = CLRS;
+        let matrices = chain(&p);
+        let (m, s) = multiplicacao_de_matrizes::<u128>(&p).unwrap();
+
+        let execucao = multiply_chain(&s, &matrices).unwrap();
+        let esquerda_para_direita = matrices[1..]
+            .iter()
+            .fold(matrices[0].clone(), |acc, x| acc.multiply(x).unwrap());
+
+        assert_eq!(execucao.result, esquerda_para_direita);
+        assert_eq!(Some(execucao.scalar_multiplications), m.get(1, 6));
+        assert_eq!(execucao.scalar_multiplications, 15125);
+    }
+
+    #[test]
+    fn test_single_matrix() {
+        let matrices = chain(&[4, 3]);
+        let (_, s) = multiplicacao_de_matrizes::<u128>(&[4, 3]).unwrap();
+        let execucao = multiply_chain(&s, &matrices).unwrap();
+        assert_eq!(execucao.result, matrices[0]);
+        assert_eq!(execucao.scalar_multiplications, 0);
+    }
+
+    #[test]
+    fn test_dimension_errors() {
+        let (_, s) = multiplicacao_de_matrizes::<u128>(&[2, 3, 4]).unwrap();
+
+        let incompativeis = vec![Matrix::from_fn(2, 3, |_, _| 1), Matrix::from_fn(5, 4, |_, _| 1)];
+        assert_eq!(
+            multiply_chain(&s, &incompativeis),
+            Err(ExecutionError::DimensionMismatch { position: 1, left_cols: 3, right_rows: 5 })
+        );
+        assert_eq!(
+            multiply_chain(&s, &chain(&[2, 3, 4, 5])),
+            Err(ExecutionError::TableSize { expected: 2, found: 3 })
+        );
+        assert_eq!(multiply_chain::<i64>(&s, &[]), Err(ExecutionError::EmptyChain));
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let erro = ExecutionError::DimensionMismatch { position: 1, left_cols: 3, right_rows: 5 };
+        assert_eq!(erro.to_string(), "A1 tem 3 colunas, mas A2 tem 5 linhas");
+        assert_eq!(erro.message(Lang::En), "A1 has 3 columns, but A2 has 5 rows");
+    }
+}
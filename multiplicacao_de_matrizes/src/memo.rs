@@ -0,0 +1,95 @@
+//! Versão recursiva com memorização (LOOKUP-CHAIN do CLRS), para ensino e
+//! depuração: segue a definição de m[i][j] de cima para baixo e só calcula
+//! as subcadeias que a recursão realmente visita.
+
+use crate::{Cost, CostOverflow, PlanError, TriangularTable, cost};
+
+struct Memo<'a, C> {
+    p: &'a [usize],
+    // Some(None) marca uma subcadeia já visitada cujo custo não cabe em C
+    m: TriangularTable<Option<C>>,
+    s: TriangularTable<usize>,
+}
+
+impl<C: Cost> Memo<'_, C> {
+    fn custo(&mut self, i: usize, j: usize) -> Option<C> {
+        if let Some(custo) = self.m.get(i, j) { // Já calculado
+            return custo;
+        }
+        let mut menor: Option<(C, usize)> = None;
+        if i == j {
+            menor = Some((C::ZERO, i));
+        }
+        for k in i..j {
+            let esquerda = self.custo(i, k);
+            let direita = self.custo(k + 1, j);
+            let q = esquerda.zip(direita).and_then(|(esquerda, direita)| {
+                cost::scalar_product::<C>(self.p[i - 1], self.p[k], self.p[j])?
+                    .checked_add(esquerda)?
+                    .checked_add(direita)
+            });
+            let Some(q) = q else { continue };
+            // Mesmo critério de desempate da versão de baixo para cima: vale o
+            // primeiro k com o menor custo
+            if menor.is_none_or(|(custo, _)| q < custo) {
+                menor = Some((q, k));
+            }
+        }
+        if let Some((_, k)) = menor
+            && i < j
+        {
+            self.s.set(i, j, k);
+        }
+        let custo = menor.map(|(custo, _)| custo);
+        self.m.set(i, j, custo);
+        custo
+    }
+}
+
+/// Mesmas tabelas de [`multiplicacao_de_matrizes`](crate::multiplicacao_de_matrizes),
+/// calculadas recursivamente com memorização. A profundidade da recursão
+/// cresce com o número de matrizes, então serve para cadeias de tamanho moderado.
+pub fn multiplicacao_de_matrizes_memoizada<C: Cost>(p: &[usize]) -> Result<(TriangularTable<C>, TriangularTable<usize>), PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    let n = p.len() - 1;
+    let mut memo = Memo { p, m: TriangularTable::new(n), s: TriangularTable::new(n) };
+    if memo.custo(1, n).is_none() {
+        return Err(PlanError::Overflow(CostOverflow::of::<C>()));
+    }
+
+    let mut m = TriangularTable::new(n);
+    for i in 1..=n {
+        for j in i..=n {
+            if let Some(Some(custo)) = memo.m.get(i, j) {
+                m.set(i, j, custo);
+            }
+        }
+    }
+    Ok((m, memo.s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multiplicacao_de_matrizes;
+    use crate::test_support::{ESTOURA_U64, dimensoes};
+
+    #[test]
+    fn test_matches_bottom_up() {
+        let mut estado: u64 = 0x853C_49E6_748F_EA9B;
+        for n in 1..=30 {
+            let p = dimensoes(&mut estado, n, 8);
+            assert_eq!(multiplicacao_de_matrizes_memoizada::<u64>(&p), multiplicacao_de_matrizes::<u64>(&p), "p = {p:?}");
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let b = ESTOURA_U64;
+        let p = vec![1, b, b, b, 1];
+        assert_eq!(multiplicacao_de_matrizes_memoizada::<u64>(&p), multiplicacao_de_matrizes::<u64>(&p));
+        assert_eq!(multiplicacao_de_matrizes_memoizada::<u64>(&[b, b, b, b]), Err(PlanError::Overflow(CostOverflow::of::<u64>())));
+    }
+}
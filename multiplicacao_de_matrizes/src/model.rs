@@ -0,0 +1,212 @@
+use crate::{Cost, cost};
+
+/// Modelo de custo para multiplicar duas matrizes intermediárias da cadeia.
+///
+/// Além das dimensões, o modelo pode acompanhar uma informação própria de
+/// cada subcadeia (o `Operand`), como a densidade estimada do produto
+/// Aᵢ…Aⱼ. Para que a programação dinâmica continue exata, essa informação
+/// só pode depender da subcadeia e não da associação escolhida: ela é
+/// calculada sempre da esquerda para a direita, combinando Aᵢ…Aⱼ₋₁ com Aⱼ.
+pub trait CostModel {
+    type Operand: Copy;
+
+    /// Informação da matriz `Aᵢ` sozinha (`index` começa em 1).
+    fn operand(&self, index: usize) -> Self::Operand;
+
+    /// Informação do produto de uma matriz `rows`×`inner` descrita por `left`
+    /// por uma `inner`×`cols` descrita por `right`.
+    fn combine(&self, rows: usize, inner: usize, cols: usize, left: Self::Operand, right: Self::Operand) -> Self::Operand;
+
+    /// Custo desse mesmo produto, ou `None` se ele não cabe em `C`.
+    fn product<C: Cost>(&self, rows: usize, inner: usize, cols: usize, left: Self::Operand, right: Self::Operand) -> Option<C>;
+}
+
+/// Multiplicação ingênua de matrizes densas: p[i-1]·p[k]·p[j] multiplicações
+/// escalares, o modelo original do programa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Naive;
+
+impl CostModel for Naive {
+    type Operand = ();
+
+    fn operand(&self, _: usize) {}
+
+    fn combine(&self, _: usize, _: usize, _: usize, _: (), _: ()) {}
+
+    fn product<C: Cost>(&self, rows: usize, inner: usize, cols: usize, _: (), _: ()) -> Option<C> {
+        cost::scalar_product(rows, inner, cols)
+    }
+}
+
+/// Strassen recursivo: enquanto todas as dimensões passam de `threshold`, as
+/// matrizes são divididas em quatro blocos (completando com zeros) e o produto
+/// usa 7 multiplicações de blocos em vez de 8. Abaixo do limiar o kernel
+/// ingênuo assume. Como em [`Naive`], só as multiplicações escalares contam.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strassen {
+    // Privado para que todo limiar passe pela verificação de `new`: com 0 a
+    // recursão nunca pararia
+    threshold: usize,
+}
+
+impl Strassen {
+    pub fn new(threshold: usize) -> Self {
+        assert!(threshold >= 1, "o limiar de Strassen precisa ser pelo menos 1");
+        Strassen { threshold }
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl CostModel for Strassen {
+    type Operand = ();
+
+    fn operand(&self, _: usize) {}
+
+    fn combine(&self, _: usize, _: usize, _: usize, _: (), _: ()) {}
+
+    fn product<C: Cost>(&self, rows: usize, inner: usize, cols: usize, _: (), _: ()) -> Option<C> {
+        if rows.min(inner).min(cols) <= self.threshold {
+            return cost::scalar_product(rows, inner, cols);
+        }
+        let metade = |d: usize| d.div_ceil(2);
+        let bloco: C = self.product(metade(rows), metade(inner), metade(cols), (), ())?;
+        bloco.checked_mul(C::from_dimension(7)?)
+    }
+}
+
+/// Palavras trocadas entre a memória e um cache de `cache_words` palavras.
+/// Se as três matrizes cabem juntas no cache, cada uma passa por ele uma vez.
+/// Senão o produto é feito em blocos b×b com 3b² ≤ `cache_words`: cada bloco
+/// do resultado lê uma faixa de `inner`×b de cada operando e é escrito uma vez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryTraffic {
+    // Privado pelo mesmo motivo: com menos de 3 palavras o bloco teria lado 0
+    cache_words: usize,
+}
+
+impl MemoryTraffic {
+    pub fn new(cache_words: usize) -> Self {
+        assert!(cache_words >= 3, "o cache precisa guardar pelo menos um elemento de cada matriz");
+        MemoryTraffic { cache_words }
+    }
+
+    pub fn cache_words(&self) -> usize {
+        self.cache_words
+    }
+}
+
+impl CostModel for MemoryTraffic {
+    type Operand = ();
+
+    fn operand(&self, _: usize) {}
+
+    fn combine(&self, _: usize, _: usize, _: usize, _: (), _: ()) {}
+
+    fn product<C: Cost>(&self, rows: usize, inner: usize, cols: usize, _: (), _: ()) -> Option<C> {
+        let area = |a: usize, b: usize| C::from_dimension(a)?.checked_mul(C::from_dimension(b)?);
+        let resultado = area(rows, cols)?;
+        let juntas = area(rows, inner)?
+            .checked_add(area(inner, cols)?)?
+            .checked_add(resultado)?;
+        if juntas <= C::from_dimension(self.cache_words)? {
+            return Some(juntas);
+        }
+        let b = (self.cache_words / 3).isqrt();
+        let blocos = area(rows.div_ceil(b), cols.div_ceil(b))?;
+        let por_bloco = C::from_dimension(2)?
+            .checked_mul(area(inner, b)?)?;
+        blocos.checked_mul(por_bloco)?.checked_add(resultado)
+    }
+}
+
+/// Matrizes esparsas com a densidade (fração de elementos não nulos) de cada
+/// matriz da cadeia. Supondo os não nulos espalhados ao acaso, o produto de
+/// uma `rows`×`inner` com densidade dₑ por uma `inner`×`cols` com densidade
+/// dᵈ faz em média rows·inner·cols·dₑ·dᵈ multiplicações e tem densidade
+/// 1 - (1 - dₑ·dᵈ)^inner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sparse {
+    densities: Vec<f64>,
+}
+
+impl Sparse {
+    /// `densities[i]` é a densidade de Aᵢ₊₁.
+    pub fn new(densities: Vec<f64>) -> Self {
+        assert!(
+            densities.iter().all(|d| (0.0..=1.0).contains(d)),
+            "as densidades precisam estar entre 0 e 1"
+        );
+        Sparse { densities }
+    }
+
+    pub fn densities(&self) -> &[f64] {
+        &self.densities
+    }
+}
+
+impl CostModel for Sparse {
+    type Operand = f64;
+
+    fn operand(&self, index: usize) -> f64 {
+        assert!(
+            index <= self.densities.len(),
+            "falta a densidade da matriz A{index} (só há {})",
+            self.densities.len()
+        );
+        self.densities[index - 1]
+    }
+
+    fn combine(&self, _: usize, inner: usize, _: usize, left: f64, right: f64) -> f64 {
+        1.0 - (1.0 - left * right).powf(inner as f64)
+    }
+
+    fn product<C: Cost>(&self, rows: usize, inner: usize, cols: usize, left: f64, right: f64) -> Option<C> {
+        let esperado = (rows as f64 * inner as f64 * cols as f64 * left * right).ceil();
+        // Acima de 2⁶⁴ a conversão para usize satura, então tratamos como estouro
+        if esperado >= usize::MAX as f64 {
+            return None;
+        }
+        C::from_dimension(esperado as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strassen_threshold() {
+        let modelo = Strassen::new(64);
+        assert_eq!(modelo.threshold(), 64);
+        // Abaixo do limiar o custo é o ingênuo
+        assert_eq!(modelo.product::<u64>(64, 128, 128, (), ()), Some(64 * 128 * 128));
+        // 128 → 64: um nível de Strassen
+        assert_eq!(modelo.product::<u64>(128, 128, 128, (), ()), Some(7 * 64 * 64 * 64));
+        // 200 → 100 → 50: dois níveis, o segundo já no kernel ingênuo
+        assert_eq!(modelo.product::<u64>(200, 200, 200, (), ()), Some(49 * 50 * 50 * 50));
+    }
+
+    #[test]
+    fn test_memory_traffic() {
+        let modelo = MemoryTraffic::new(300);
+        assert_eq!(modelo.cache_words(), 300);
+        // 10·10 + 10·10 + 10·10 = 300 cabe no cache
+        assert_eq!(modelo.product::<u64>(10, 10, 10, (), ()), Some(300));
+        // b = 10: 2×2 blocos do resultado, cada um lê 2·20·10 palavras
+        assert_eq!(modelo.product::<u64>(20, 20, 20, (), ()), Some(4 * 400 + 400));
+    }
+
+    #[test]
+    fn test_sparse_density() {
+        let modelo = Sparse::new(vec![0.5, 0.1]);
+        assert_eq!(modelo.product::<u64>(10, 10, 10, 0.5, 0.1), Some(50));
+        let densidade = modelo.combine(10, 10, 10, 0.5, 0.1);
+        assert!((densidade - (1.0 - 0.95f64.powi(10))).abs() < 1e-12);
+        // Matrizes densas multiplicadas continuam densas
+        assert_eq!(modelo.combine(3, 4, 5, 1.0, 1.0), 1.0);
+        assert_eq!(modelo.product::<u64>(3, 4, 5, 1.0, 1.0), Some(60));
+    }
+}
@@ -0,0 +1,278 @@
+//! Cadeias com matrizes nomeadas e dimensões simbólicas, como
+//! `X: n x d, W: d x k, b: k x 1`. As dimensões podem ser números ou nomes;
+//! os nomes recebem valores depois ([`NamedChain::dimensions`]), então a
+//! mesma cadeia pode ser replanejada para tamanhos diferentes.
+//!
+//! Entre as dimensões vale `x`, `X` ou `×`. Sem espaços (`nxd`) a divisão é
+//! no primeiro `x`, então símbolos que contêm a letra x precisam de espaços
+//! em volta do separador (`max x d`).
+
+use std::{collections::HashMap, fmt};
+
+use mensagens::Lang;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dim {
+    Known(usize),
+    Symbol(String),
+}
+
+impl fmt::Display for Dim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dim::Known(d) => write!(f, "{d}"),
+            Dim::Symbol(nome) => write!(f, "{nome}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedMatrix {
+    pub name: String,
+    pub rows: Dim,
+    pub cols: Dim,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamedErrorKind {
+    Empty,
+    // Falta o `nome:` antes das dimensões
+    MissingName,
+    DuplicateName(String),
+    // As dimensões não estão no formato `linhas x colunas`
+    InvalidDimensions(String),
+    ZeroDimension,
+    // Um valor em `n=10` que não é um número positivo, ou sem o `=`
+    InvalidBinding(String),
+    UnboundSymbol(String),
+    // As colunas de uma matriz não batem com as linhas da seguinte
+    DimensionMismatch { left: String, right: String, cols: usize, rows: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedError {
+    pub kind: NamedErrorKind,
+    // Posição (a partir de 1) da matriz ou associação com problema; 0 quando
+    // o erro é da cadeia toda
+    pub entry: usize,
+}
+
+impl NamedError {
+    pub fn message(&self, lang: Lang) -> String {
+        let motivo = match &self.kind {
+            NamedErrorKind::Empty => return lang.format("nomeada.vazia", &[]),
+            NamedErrorKind::MissingName => lang.format("nomeada.sem_nome", &[]),
+            NamedErrorKind::DuplicateName(nome) => lang.format("nomeada.nome_repetido", &[nome]),
+            NamedErrorKind::InvalidDimensions(texto) => lang.format("nomeada.dimensoes_invalidas", &[texto]),
+            NamedErrorKind::ZeroDimension => lang.format("entrada.dimensao_zero", &[]),
+            NamedErrorKind::InvalidBinding(texto) => lang.format("nomeada.valor_invalido", &[texto]),
+            NamedErrorKind::UnboundSymbol(nome) => lang.format("nomeada.simbolo_sem_valor", &[nome]),
+            NamedErrorKind::DimensionMismatch { left, right, cols, rows } => {
+                lang.format("nomeada.dimensoes_incompativeis", &[left, cols, right, rows])
+            }
+        };
+        lang.format("nomeada.erro_no_item", &[&self.entry, &motivo])
+    }
+}
+
+impl fmt::Display for NamedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for NamedError {}
+
+/// Cadeia de matrizes nomeadas, na ordem em que são multiplicadas. Só é
+/// criada por [`NamedChain::parse`], que garante pelo menos uma matriz.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedChain {
+    matrices: Vec<NamedMatrix>,
+}
+
+fn erro(kind: NamedErrorKind, entry: usize) -> NamedError {
+    NamedError { kind, entry }
+}
+
+fn dimensao(texto: &str, entry: usize) -> Result<Dim, NamedError> {
+    let texto = texto.trim();
+    let simbolo = texto.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && texto.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if simbolo {
+        return Ok(Dim::Symbol(texto.to_owned()));
+    }
+    match texto.parse::<usize>() {
+        Ok(0) => Err(erro(NamedErrorKind::ZeroDimension, entry)),
+        Ok(d) => Ok(Dim::Known(d)),
+        Err(_) => Err(erro(NamedErrorKind::InvalidDimensions(texto.to_owned()), entry)),
+    }
+}
+
+// Divide `linhas x colunas`, preferindo um separador cercado de espaços
+fn divide_dimensoes(texto: &str) -> Option<(&str, &str)> {
+    if let Some(par) = texto.split_once('×') {
+        return Some(par);
+    }
+    let palavras: Vec<&str> = texto.split_whitespace().collect();
+    if let [linhas, "x" | "X", colunas] = palavras[..] {
+        return Some((linhas, colunas));
+    }
+    texto.split_once(['x', 'X'])
+}
+
+impl NamedChain {
+    /// Lê uma cadeia como `X: 10x20, W: 20x5, b: 5x1`.
+    pub fn parse(line: &str) -> Result<Self, NamedError> {
+        // Uma linha em branco ainda rende um item vazio no split
+        if line.trim().is_empty() {
+            return Err(erro(NamedErrorKind::Empty, 0));
+        }
+        let mut matrices: Vec<NamedMatrix> = Vec::new();
+        for (entry, item) in (1..).zip(line.split(',')) {
+            let Some((nome, dimensoes)) = item.split_once(':') else {
+                return Err(erro(NamedErrorKind::MissingName, entry));
+            };
+            let nome = nome.trim();
+            if nome.is_empty() {
+                return Err(erro(NamedErrorKind::MissingName, entry));
+            }
+            if matrices.iter().any(|m| m.name == nome) {
+                return Err(erro(NamedErrorKind::DuplicateName(nome.to_owned()), entry));
+            }
+            let Some((linhas, colunas)) = divide_dimensoes(dimensoes) else {
+                return Err(erro(NamedErrorKind::InvalidDimensions(dimensoes.trim().to_owned()), entry));
+            };
+            matrices.push(NamedMatrix {
+                name: nome.to_owned(),
+                rows: dimensao(linhas, entry)?,
+                cols: dimensao(colunas, entry)?,
+            });
+        }
+        Ok(NamedChain { matrices })
+    }
+
+    pub fn matrices(&self) -> &[NamedMatrix] {
+        &self.matrices
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.matrices.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    /// Símbolos usados nas dimensões, na ordem em que aparecem pela primeira vez.
+    pub fn symbols(&self) -> Vec<&str> {
+        let mut simbolos: Vec<&str> = Vec::new();
+        for m in &self.matrices {
+            for d in [&m.rows, &m.cols] {
+                if let Dim::Symbol(nome) = d
+                    && !simbolos.contains(&nome.as_str())
+                {
+                    simbolos.push(nome);
+                }
+            }
+        }
+        simbolos
+    }
+
+    /// O vetor de dimensões `p` com os símbolos trocados pelos valores de
+    /// `bindings`, conferindo que matrizes vizinhas podem ser multiplicadas.
+    pub fn dimensions(&self, bindings: &HashMap<String, usize>) -> Result<Vec<usize>, NamedError> {
+        let valor = |d: &Dim, entry: usize| match d {
+            Dim::Known(d) => Ok(*d),
+            Dim::Symbol(nome) => bindings
+                .get(nome)
+                .copied()
+                .ok_or_else(|| erro(NamedErrorKind::UnboundSymbol(nome.clone()), entry)),
+        };
+        let mut p = vec![valor(&self.matrices[0].rows, 1)?];
+        for (entry, par) in (1..).zip(self.matrices.windows(2)) {
+            let colunas = valor(&par[0].cols, entry)?;
+            let linhas = valor(&par[1].rows, entry + 1)?;
+            if colunas != linhas {
+                return Err(erro(
+                    NamedErrorKind::DimensionMismatch {
+                        left: par[0].name.clone(),
+                        right: par[1].name.clone(),
+                        cols: colunas,
+                        rows: linhas,
+                    },
+                    entry + 1,
+                ));
+            }
+            p.push(colunas);
+        }
+        p.push(valor(&self.matrices.last().unwrap().cols, self.matrices.len())?);
+        Ok(p)
+    }
+}
+
+/// Lê valores para os símbolos, como `n=1000, d=50`.
+pub fn parse_bindings(line: &str) -> Result<HashMap<String, usize>, NamedError> {
+    let mut valores = HashMap::new();
+    for (entry, item) in (1..).zip(line.split(',')) {
+        if item.trim().is_empty() {
+            continue;
+        }
+        let invalido = || erro(NamedErrorKind::InvalidBinding(item.trim().to_owned()), entry);
+        let (nome, valor) = item.split_once('=').ok_or_else(invalido)?;
+        let valor = valor.trim().parse::<usize>().ok().filter(|&v| v > 0).ok_or_else(invalido)?;
+        valores.insert(nome.trim().to_owned(), valor);
+    }
+    Ok(valores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan;
+
+    #[test]
+    fn test_parse_numeric() {
+        let cadeia = NamedChain::parse("X: 10x20, W: 20 x 5, b: 5×1").unwrap();
+        assert_eq!(cadeia.names(), ["X", "W", "b"]);
+        assert_eq!(cadeia.matrices()[1], NamedMatrix { name: "W".to_owned(), rows: Dim::Known(20), cols: Dim::Known(5) });
+        assert_eq!(cadeia.symbols(), Vec::<&str>::new());
+        assert_eq!(cadeia.dimensions(&HashMap::new()), Ok(vec![10, 20, 5, 1]));
+    }
+
+    #[test]
+    fn test_symbols_replan() {
+        let cadeia = NamedChain::parse("X: n x d, W: d x k, b: k x 1").unwrap();
+        assert_eq!(cadeia.symbols(), ["n", "d", "k"]);
+
+        // Poucas linhas em X: melhor começar pela esquerda
+        let p = cadeia.dimensions(&parse_bindings("n=1, d=50, k=40").unwrap()).unwrap();
+        assert_eq!(p, [1, 50, 40, 1]);
+        assert_eq!(plan(&p).unwrap().tree.render_named(&cadeia.names()), "(X∙W)∙b");
+
+        // Muitas linhas em X: melhor começar pela direita
+        let p = cadeia.dimensions(&parse_bindings("n=1000, d=50, k=40").unwrap()).unwrap();
+        assert_eq!(plan(&p).unwrap().tree.render_named(&cadeia.names()), "X∙(W∙b)");
+    }
+
+    #[test]
+    fn test_errors() {
+        let kind = |linha: &str| NamedChain::parse(linha).unwrap_err().kind;
+        assert_eq!(kind(""), NamedErrorKind::Empty);
+        assert_eq!(kind(" \n"), NamedErrorKind::Empty);
+        assert_eq!(kind("10x20"), NamedErrorKind::MissingName);
+        assert_eq!(kind(": 10x20"), NamedErrorKind::MissingName);
+        assert_eq!(kind("X: 10x20, X: 20x5"), NamedErrorKind::DuplicateName("X".to_owned()));
+        assert_eq!(kind("X: 10"), NamedErrorKind::InvalidDimensions("10".to_owned()));
+        assert_eq!(kind("X: 10x0"), NamedErrorKind::ZeroDimension);
+        assert_eq!(kind("X: 1-0x2"), NamedErrorKind::InvalidDimensions("1-0".to_owned()));
+
+        let cadeia = NamedChain::parse("X: n x d, W: 20 x 5").unwrap();
+        let erro = cadeia.dimensions(&parse_bindings("n=3").unwrap()).unwrap_err();
+        assert_eq!(erro, NamedError { kind: NamedErrorKind::UnboundSymbol("d".to_owned()), entry: 1 });
+        let erro = cadeia.dimensions(&parse_bindings("n=3, d=7").unwrap()).unwrap_err();
+        assert_eq!(
+            erro.kind,
+            NamedErrorKind::DimensionMismatch { left: "X".to_owned(), right: "W".to_owned(), cols: 7, rows: 20 }
+        );
+        assert_eq!(erro.entry, 2);
+
+        assert_eq!(parse_bindings("n").unwrap_err().kind, NamedErrorKind::InvalidBinding("n".to_owned()));
+        assert_eq!(parse_bindings("n=0").unwrap_err().kind, NamedErrorKind::InvalidBinding("n=0".to_owned()));
+    }
+}
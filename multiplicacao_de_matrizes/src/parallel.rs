@@ -0,0 +1,102 @@
+//! Programação dinâmica por intervalos calculada por diagonais (frente de onda).
+//!
+//! Todos os intervalos de um mesmo tamanho l dependem só dos menores, então
+//! cada diagonal da tabela é calculada em paralelo no pool do rayon e escrita
+//! de uma vez antes da próxima. Cada célula faz as mesmas contas da versão
+//! sequencial, na mesma ordem, então as tabelas m e s saem idênticas.
+
+use rayon::prelude::*;
+
+use crate::interval::{IntervalProblem, calcula_celula, tabela_de_custos};
+use crate::{Cost, CostModel, CostOverflow, MatrixChain, Naive, PlanError, TriangularTable};
+
+// Abaixo disso uma diagonal é calculada numa thread só
+const CELULAS_POR_TAREFA: usize = 16;
+
+/// Versão paralela de [`multiplicacao_de_matrizes`](crate::multiplicacao_de_matrizes).
+pub fn multiplicacao_de_matrizes_paralela<C: Cost + Send + Sync>(p: &[usize]) -> Result<(TriangularTable<C>, TriangularTable<usize>), PlanError> {
+    multiplicacao_de_matrizes_paralela_com(p, &Naive)
+}
+
+/// Versão paralela de [`multiplicacao_de_matrizes_com`](crate::multiplicacao_de_matrizes_com).
+pub fn multiplicacao_de_matrizes_paralela_com<C, M>(p: &[usize], modelo: &M) -> Result<(TriangularTable<C>, TriangularTable<usize>), PlanError>
+where
+    C: Cost + Send + Sync,
+    M: CostModel + Sync,
+    M::Operand: Send + Sync,
+{
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    Ok(solve_parallel(&MatrixChain { p, model: modelo })?)
+}
+
+/// Versão paralela de [`interval::solve`](crate::interval::solve).
+pub fn solve_parallel<C, P>(problema: &P) -> Result<(TriangularTable<C>, TriangularTable<usize>), CostOverflow>
+where
+    C: Cost + Send + Sync,
+    P: IntervalProblem<C> + Sync,
+    P::Info: Send + Sync,
+{
+    let n = problema.size();
+    let mut celulas = TriangularTable::new(n);
+    let mut espelho = TriangularTable::new(n);
+    let mut s = TriangularTable::new(n);
+
+    for i in 1..=n {
+        let celula = (problema.base(i), problema.info(i));
+        celulas.set(i, i, celula);
+        espelho.set(n+1-i, n+1-i, celula);
+    }
+
+    for l in 2..=n {
+        let diagonal: Vec<_> = (1..n-l+2)
+            .into_par_iter()
+            .with_min_len(CELULAS_POR_TAREFA)
+            .map(|i| calcula_celula(problema, &celulas, &espelho, i, i + l - 1, i..i + l - 1))
+            .collect();
+        for (i, (celula, k)) in (1..).zip(diagonal) {
+            let j = i + l - 1;
+            if let Some(k) = k {
+                s.set(i, j, k);
+            }
+            celulas.set(i, j, celula);
+            espelho.set(n+1-j, n+1-i, celula);
+        }
+    }
+
+    tabela_de_custos(&celulas).map(|m| (m, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ESTOURA_U64, dimensoes};
+    use crate::{Sparse, multiplicacao_de_matrizes, multiplicacao_de_matrizes_com};
+
+    #[test]
+    fn test_matches_sequential() {
+        for n in [1, 2, 3, 17, 64, 150] {
+            // Dimensões pequenas para forçar empates
+            let p = dimensoes(&mut (0x9E37_79B9_7F4A_7C15 ^ n as u64), n, 6);
+            assert_eq!(multiplicacao_de_matrizes_paralela::<u64>(&p), multiplicacao_de_matrizes::<u64>(&p));
+        }
+    }
+
+    #[test]
+    fn test_matches_sequential_with_model() {
+        let p = dimensoes(&mut 42, 80, 6);
+        let modelo = Sparse::new((0..80).map(|i| 1.0 / (i % 7 + 1) as f64).collect());
+        assert_eq!(
+            multiplicacao_de_matrizes_paralela_com::<u64, _>(&p, &modelo),
+            multiplicacao_de_matrizes_com::<u64, _>(&p, &modelo)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let b = ESTOURA_U64;
+        let p = vec![b, b, b, b];
+        assert_eq!(multiplicacao_de_matrizes_paralela::<u64>(&p), Err(PlanError::Overflow(CostOverflow::of::<u64>())));
+    }
+}
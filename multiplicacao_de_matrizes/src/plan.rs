@@ -0,0 +1,183 @@
+//! API estável para quem só quer a melhor ordem de uma cadeia, sem lidar com
+//! as tabelas da programação dinâmica nem escolher o tipo do custo.
+//!
+//! Além do custo, o planejador pode levar em conta a memória: o pico de uma
+//! ordem é o tamanho (p[i-1]·p[j] elementos) do maior produto que ela calcula,
+//! contando o resultado final. [`pareto_plans`] devolve as ordens em que não
+//! dá para gastar menos memória sem fazer mais multiplicações, e
+//! [`plan_within`] a mais barata que respeita um teto de memória.
+
+use std::fmt;
+
+use mensagens::Lang;
+
+use crate::{CostOverflow, ParenTree, cost, multiplicacao_de_matrizes};
+
+/// A melhor forma de multiplicar uma cadeia: quantas multiplicações escalares
+/// ela faz e em que ordem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainPlan {
+    pub cost: u128,
+    pub tree: ParenTree,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// `p` precisa de pelo menos duas dimensões, uma matriz (na triangulação,
+    /// o polígono precisa de pelo menos três vértices).
+    TooFewDimensions,
+    /// Na árvore de busca ótima, `keys` chaves pedem `keys + 1` frequências
+    /// de buscas sem sucesso, mas vieram `dummies`.
+    MismatchedFrequencies { keys: usize, dummies: usize },
+    /// O custo mínimo não cabe nem em u128.
+    Overflow(CostOverflow),
+    /// Nenhuma ordem cabe em `ceiling` elementos; a mais econômica precisa de `minimum`.
+    MemoryCeiling { ceiling: u128, minimum: u128 },
+}
+
+impl PlanError {
+    pub fn message(&self, lang: Lang) -> String {
+        match self {
+            PlanError::TooFewDimensions => lang.format("plano.poucas_dimensoes", &[]),
+            PlanError::MismatchedFrequencies { keys, dummies } => {
+                lang.format("plano.frequencias_incompativeis", &[keys, &(keys + 1), dummies])
+            }
+            PlanError::Overflow(erro) => erro.message(lang),
+            PlanError::MemoryCeiling { ceiling, minimum } => {
+                lang.format("plano.teto_de_memoria", &[ceiling, minimum])
+            }
+        }
+    }
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message(Lang::PtBr))
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl From<CostOverflow> for PlanError {
+    fn from(erro: CostOverflow) -> Self {
+        PlanError::Overflow(erro)
+    }
+}
+
+/// Melhor ordem para a cadeia em que a matriz Aᵢ tem dimensões p[i-1]×p[i].
+pub fn plan(p: &[usize]) -> Result<ChainPlan, PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    let n = p.len() - 1;
+    let (m, s) = multiplicacao_de_matrizes::<u128>(p)?;
+    Ok(ChainPlan {
+        cost: m.get(1, n).unwrap(),
+        tree: ParenTree::from_splits(&s, 1, n),
+    })
+}
+
+/// Uma ordem com o custo e o pico de memória dela, em elementos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryPlan {
+    pub cost: u128,
+    pub peak: u128,
+    pub tree: ParenTree,
+}
+
+// Um ponto da fronteira de Aᵢ…Aⱼ: a divisão k e qual ponto da fronteira de
+// cada lado foi usado
+#[derive(Clone, Copy)]
+struct Ponto {
+    custo: u128,
+    pico: u128,
+    k: usize,
+    esquerda: usize,
+    direita: usize,
+}
+
+// fronteiras[i][j]: os pontos não dominados de Aᵢ…Aⱼ, do mais barato (e
+// mais pesado) para o mais caro (e mais leve)
+fn fronteiras(p: &[usize]) -> Vec<Vec<Vec<Ponto>>> {
+    let n = p.len() - 1;
+    let mut fronteiras = vec![vec![Vec::new(); n + 1]; n + 1];
+    for (i, linha) in fronteiras.iter_mut().enumerate().skip(1) {
+        linha[i].push(Ponto { custo: 0, pico: 0, k: i, esquerda: 0, direita: 0 });
+    }
+    for l in 2..=n {
+        for i in 1..=n - l + 1 {
+            let j = i + l - 1;
+            // O tamanho de um produto cabe em u128 (usize tem no máximo 64
+            // bits), mas p[i-1]·p[k]·p[j] pode não caber
+            let tamanho = p[i - 1] as u128 * p[j] as u128;
+            let mut candidatos = Vec::new();
+            for k in i..j {
+                // Custos que estouram u128 ficam de fora, como na programação dinâmica
+                let Some(produto) = cost::scalar_product::<u128>(p[i - 1], p[k], p[j]) else {
+                    continue;
+                };
+                for (a, esquerda) in fronteiras[i][k].iter().enumerate() {
+                    for (b, direita) in fronteiras[k + 1][j].iter().enumerate() {
+                        let Some(custo) = esquerda.custo.checked_add(direita.custo).and_then(|c| c.checked_add(produto))
+                        else {
+                            continue;
+                        };
+                        let pico = tamanho.max(esquerda.pico).max(direita.pico);
+                        candidatos.push(Ponto { custo, pico, k, esquerda: a, direita: b });
+                    }
+                }
+            }
+            // Ordenação estável: nos empates fica a menor divisão k
+            candidatos.sort_by_key(|ponto| (ponto.custo, ponto.pico));
+            let mut fronteira: Vec<Ponto> = Vec::new();
+            for ponto in candidatos {
+                if fronteira.last().is_none_or(|ultimo| ponto.pico < ultimo.pico) {
+                    fronteira.push(ponto);
+                }
+            }
+            fronteiras[i][j] = fronteira;
+        }
+    }
+    fronteiras
+}
+
+fn monta(fronteiras: &[Vec<Vec<Ponto>>], i: usize, j: usize, indice: usize) -> ParenTree {
+    if i == j {
+        return ParenTree::Leaf(i);
+    }
+    let ponto = fronteiras[i][j][indice];
+    ParenTree::product(
+        monta(fronteiras, i, ponto.k, ponto.esquerda),
+        monta(fronteiras, ponto.k + 1, j, ponto.direita),
+    )
+}
+
+/// Fronteira de Pareto de (custo, pico de memória): uma ordem para cada
+/// compromisso que vale a pena, da mais barata (que tem o custo de [`plan`])
+/// até a que usa menos memória. Cada uma gasta menos memória que a anterior
+/// e faz mais multiplicações.
+pub fn pareto_plans(p: &[usize]) -> Result<Vec<MemoryPlan>, PlanError> {
+    if p.len() < 2 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    let n = p.len() - 1;
+    let fronteiras = fronteiras(p);
+    if fronteiras[1][n].is_empty() {
+        return Err(PlanError::Overflow(CostOverflow::of::<u128>()));
+    }
+    Ok(fronteiras[1][n]
+        .iter()
+        .enumerate()
+        .map(|(indice, ponto)| MemoryPlan { cost: ponto.custo, peak: ponto.pico, tree: monta(&fronteiras, 1, n, indice) })
+        .collect())
+}
+
+/// A ordem mais barata cujo pico de memória não passa de `ceiling` elementos.
+pub fn plan_within(p: &[usize], ceiling: u128) -> Result<MemoryPlan, PlanError> {
+    let mut planos = pareto_plans(p)?;
+    let minimum = planos.last().unwrap().peak;
+    match planos.iter().position(|plano| plano.peak <= ceiling) {
+        Some(indice) => Ok(planos.swap_remove(indice)),
+        None => Err(PlanError::MemoryCeiling { ceiling, minimum }),
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::{Display, Write};
+
+/// Tabela triangular superior densa para os valores `t[i][j]` com
+/// `1 ≤ i ≤ j ≤ n`, indexada a partir de 1 como no pseudocódigo do CLRS.
+/// As células ainda não calculadas valem `None`, então um custo real igual a
+/// 0 não se confunde com "ainda não computado".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriangularTable<T> {
+    n: usize,
+    cells: Vec<Option<T>>,
+}
+
+impl<T: Copy> TriangularTable<T> {
+    pub fn new(n: usize) -> Self {
+        TriangularTable {
+            n,
+            cells: vec![None; n * (n + 1) / 2],
+        }
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    // As linhas são guardadas uma após a outra: a linha r (base 0) tem n - r
+    // células e começa depois de r·n - r(r - 1)/2 células
+    fn offset(&self, i: usize, j: usize) -> usize {
+        assert!(
+            1 <= i && i <= j && j <= self.n,
+            "posição ({i}, {j}) fora da tabela triangular de ordem {}",
+            self.n
+        );
+        let (r, c) = (i - 1, j - 1);
+        r * self.n - r * r.saturating_sub(1) / 2 + (c - r)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<T> {
+        self.cells[self.offset(i, j)]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        let offset = self.offset(i, j);
+        self.cells[offset] = Some(value);
+    }
+}
+
+impl<T: Copy + Display> TriangularTable<T> {
+    /// Tabela completa n×n com os índices de linha e coluna e as colunas
+    /// alinhadas à direita. A parte abaixo da diagonal fica em branco e as
+    /// células sem valor aparecem como `-`.
+    pub fn to_grid(&self) -> String {
+        let celulas: Vec<Vec<String>> = (1..=self.n)
+            .map(|i| {
+                (1..=self.n)
+                    .map(|j| {
+                        if j < i {
+                            String::new()
+                        } else {
+                            self.get(i, j).map_or("-".to_owned(), |v| v.to_string())
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let largura_indice = self.n.to_string().len();
+        let largura = celulas
+            .iter()
+            .flatten()
+            .map(|c| c.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(largura_indice);
+
+        let mut saida = " ".repeat(largura_indice);
+        for j in 1..=self.n {
+            write!(saida, " {j:>largura$}").unwrap();
+        }
+        saida.push('\n');
+        for (i, linha) in celulas.iter().enumerate() {
+            write!(saida, "{:>largura_indice$}", i + 1).unwrap();
+            for celula in linha {
+                write!(saida, " {celula:>largura$}").unwrap();
+            }
+            saida.push('\n');
+        }
+        saida
+    }
+
+    /// A mesma tabela em CSV: a primeira linha e a primeira coluna trazem os
+    /// índices, e as células abaixo da diagonal ou sem valor ficam vazias.
+    pub fn to_csv(&self) -> String {
+        let mut saida = String::new();
+        for j in 1..=self.n {
+            write!(saida, ",{j}").unwrap();
+        }
+        saida.push('\n');
+        for i in 1..=self.n {
+            write!(saida, "{i}").unwrap();
+            for j in 1..=self.n {
+                saida.push(',');
+                if let Some(valor) = (j >= i).then(|| self.get(i, j)).flatten() {
+                    write!(saida, "{valor}").unwrap();
+                }
+            }
+            saida.push('\n');
+        }
+        saida
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_cell_is_distinct() {
+        let n = 7;
+        let mut table = TriangularTable::new(n);
+        for i in 1..=n {
+            for j in i..=n {
+                assert_eq!(table.get(i, j), None);
+                table.set(i, j, (i, j));
+            }
+        }
+        for i in 1..=n {
+            for j in i..=n {
+                assert_eq!(table.get(i, j), Some((i, j)));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lower_triangle_is_out_of_bounds() {
+        let table: TriangularTable<usize> = TriangularTable::new(3);
+        table.get(2, 1);
+    }
+
+    #[test]
+    fn test_grid_and_csv() {
+        let mut table = TriangularTable::new(3);
+        table.set(1, 1, 0);
+        table.set(1, 2, 150);
+        table.set(2, 2, 0);
+        table.set(3, 3, 0);
+        assert_eq!(
+            table.to_grid(),
+            "    1   2   3\n\
+             1   0 150   -\n\
+             2       0   -\n\
+             3           0\n"
+        );
+        assert_eq!(table.to_csv(), ",1,2,3\n1,0,150,\n2,,0,\n3,,,0\n");
+    }
+}
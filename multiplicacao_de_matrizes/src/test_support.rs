@@ -0,0 +1,20 @@
+//! Dados e geradores compartilhados pelos testes dos módulos.
+
+/// Cadeia da seção 15.2 do CLRS, de custo ótimo 15125.
+pub(crate) const CLRS: [usize; 7] = [30, 35, 15, 5, 10, 20, 25];
+
+/// Dimensão cujo produto de três, 2⁶⁶, não cabe em `u64` mas cabe em `u128`.
+pub(crate) const ESTOURA_U64: usize = 1 << 22;
+
+/// Próximo valor do gerador xorshift64; `estado` não pode ser zero.
+pub(crate) fn proximo(estado: &mut u64) -> u64 {
+    *estado ^= *estado << 13;
+    *estado ^= *estado >> 7;
+    *estado ^= *estado << 17;
+    *estado
+}
+
+/// As n + 1 dimensões de uma cadeia pseudoaleatória, todas em 1..=maximo.
+pub(crate) fn dimensoes(estado: &mut u64, n: usize, maximo: usize) -> Vec<usize> {
+    (0..=n).map(|_| (proximo(estado) % maximo as u64 + 1) as usize).collect()
+}
@@ -0,0 +1,323 @@
+use std::mem;
+
+use crate::{Cost, TriangularTable, cost, format};
+
+/// Árvore de associação de um produto de matrizes: as folhas são os índices
+/// das matrizes (a partir de 1) e cada nó interno é o produto de duas
+/// subárvores.
+///
+/// Numa cadeia torta a profundidade chega a n, então a construção
+/// ([`from_splits`](Self::from_splits)), [`cost`](Self::cost),
+/// [`range`](Self::range) e a destruição usam pilhas explícitas e aceitam
+/// cadeias de qualquer tamanho. As demais operações (desenhar, comparar,
+/// clonar) são recursivas e servem para árvores de até alguns milhares de níveis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParenTree {
+    Leaf(usize),
+    Product(Box<ParenTree>, Box<ParenTree>),
+}
+
+/// Formas de escrever a expressão de uma [`ParenTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// `A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅)`, a saída original do programa.
+    Unicode,
+    /// `A_30x35 * (A_35x15 * A_15x5)`.
+    Ascii,
+    /// `A_{30 \times 35} \cdot \left(A_{35 \times 15} \cdot A_{15 \times 5}\right)`.
+    Latex,
+    /// `(A1(A2A3))`: todo produto entre parênteses, inclusive o mais externo.
+    FullyParenthesized,
+}
+
+impl Notation {
+    /// `Unicode` se a saída aceita UTF-8 ([`format::utf8_output`]), senão `Ascii`.
+    pub fn auto() -> Self {
+        if format::utf8_output() { Notation::Unicode } else { Notation::Ascii }
+    }
+
+    // Abre parênteses, operador do produto e fecha parênteses
+    fn simbolos(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Notation::Unicode => ("(", "∙", ")"),
+            Notation::Ascii => ("(", " * ", ")"),
+            Notation::Latex => ("\\left(", " \\cdot ", "\\right)"),
+            Notation::FullyParenthesized => ("(", "", ")"),
+        }
+    }
+}
+
+impl ParenTree {
+    pub fn product(left: ParenTree, right: ParenTree) -> Self {
+        ParenTree::Product(Box::new(left), Box::new(right))
+    }
+
+    /// Reconstrói a associação de Aᵢ…Aⱼ a partir da tabela de divisões `s`.
+    pub fn from_splits(s: &TriangularTable<usize>, i: usize, j: usize) -> Self {
+        ParenTree::from_split_fn(i, j, |i, j| s.get(i, j).expect("s[i][j] deve estar calculado para i < j"))
+    }
+
+    // Associação de Aᵢ…Aⱼ em que cada Aᵢ…Aⱼ com i < j é dividido em Aᵢ…Aₖ e
+    // Aₖ₊₁…Aⱼ, com k = divisao(i, j). Monta a árvore de baixo para cima com
+    // uma pilha, sem recursão.
+    pub(crate) fn from_split_fn(i: usize, j: usize, divisao: impl Fn(usize, usize) -> usize) -> Self {
+        enum Passo {
+            Visita(usize, usize),
+            Junta,
+        }
+        let mut pendentes = vec![Passo::Visita(i, j)];
+        let mut prontas = Vec::new();
+        while let Some(passo) = pendentes.pop() {
+            match passo {
+                Passo::Visita(i, j) if i == j => prontas.push(ParenTree::Leaf(i)),
+                Passo::Visita(i, j) => {
+                    let k = divisao(i, j);
+                    // A esquerda sai da pilha primeiro e fica embaixo em `prontas`
+                    pendentes.extend([Passo::Junta, Passo::Visita(k + 1, j), Passo::Visita(i, k)]);
+                }
+                Passo::Junta => {
+                    let direita = prontas.pop().unwrap();
+                    let esquerda = prontas.pop().unwrap();
+                    prontas.push(ParenTree::product(esquerda, direita));
+                }
+            }
+        }
+        prontas.pop().unwrap()
+    }
+
+    /// Todas as associações de Aᵢ…Aⱼ com o custo mínimo da tabela `m`, e não
+    /// só a que ficou guardada em `s`. A quantidade cresce exponencialmente
+    /// quando há muitos empates (numa cadeia de matrizes 1×1, todas as
+    /// associações são ótimas).
+    pub fn all_optimal<C: Cost>(p: &[usize], m: &TriangularTable<C>, i: usize, j: usize) -> Vec<Self> {
+        if i == j {
+            return vec![ParenTree::Leaf(i)];
+        }
+        let Some(otimo) = m.get(i, j) else { return Vec::new() };
+        let mut arvores = Vec::new();
+        for k in i..j {
+            let custo = m.get(i, k).zip(m.get(k + 1, j)).and_then(|(esquerda, direita)| {
+                cost::scalar_product::<C>(p[i - 1], p[k], p[j])?
+                    .checked_add(esquerda)?
+                    .checked_add(direita)
+            });
+            if custo != Some(otimo) {
+                continue;
+            }
+            let direitas = ParenTree::all_optimal(p, m, k + 1, j);
+            for esquerda in ParenTree::all_optimal(p, m, i, k) {
+                for direita in &direitas {
+                    arvores.push(ParenTree::product(esquerda.clone(), direita.clone()));
+                }
+            }
+        }
+        arvores
+    }
+
+    // Primeira e última matriz cobertas pela árvore
+    pub fn range(&self) -> (usize, usize) {
+        let mut primeira = self;
+        while let ParenTree::Product(esquerda, _) = primeira {
+            primeira = esquerda;
+        }
+        let mut ultima = self;
+        while let ParenTree::Product(_, direita) = ultima {
+            ultima = direita;
+        }
+        match (primeira, ultima) {
+            (ParenTree::Leaf(i), ParenTree::Leaf(j)) => (*i, *j),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Quantidade de multiplicações escalares para calcular o produto nessa
+    /// ordem, ou `None` se ela não cabe em `C`.
+    pub fn cost<C: Cost>(&self, p: &[usize]) -> Option<C> {
+        self.cost_and_range(p).map(|(custo, _)| custo)
+    }
+
+    // Calcula o custo e o intervalo juntos para visitar cada nó uma vez só,
+    // em pós-ordem com uma pilha
+    fn cost_and_range<C: Cost>(&self, p: &[usize]) -> Option<(C, (usize, usize))> {
+        enum Passo<'a> {
+            Visita(&'a ParenTree),
+            Junta,
+        }
+        let mut pendentes = vec![Passo::Visita(self)];
+        let mut prontos: Vec<(C, (usize, usize))> = Vec::new();
+        while let Some(passo) = pendentes.pop() {
+            match passo {
+                Passo::Visita(ParenTree::Leaf(i)) => prontos.push((C::ZERO, (*i, *i))),
+                Passo::Visita(ParenTree::Product(esquerda, direita)) => {
+                    pendentes.extend([Passo::Junta, Passo::Visita(direita), Passo::Visita(esquerda)]);
+                }
+                Passo::Junta => {
+                    let (custo_direita, (_, j)) = prontos.pop().unwrap();
+                    let (custo_esquerda, (i, k)) = prontos.pop().unwrap();
+                    let custo = cost::scalar_product::<C>(p[i - 1], p[k], p[j])?
+                        .checked_add(custo_esquerda)?
+                        .checked_add(custo_direita)?;
+                    prontos.push((custo, (i, j)));
+                }
+            }
+        }
+        prontos.pop()
+    }
+
+    pub fn render(&self, p: &[usize], notation: Notation) -> String {
+        let mut saida = String::new();
+        self.write(notation, true, &mut saida, &|i, saida: &mut String| {
+            let (linhas, colunas) = (p[i - 1], p[i]);
+            match notation {
+                Notation::Unicode => saida.push_str(&format::matrix_label(linhas, colunas, true)),
+                Notation::Ascii => saida.push_str(&format::matrix_label(linhas, colunas, false)),
+                Notation::Latex => saida.push_str(&format!("A_{{{linhas} \\times {colunas}}}")),
+                Notation::FullyParenthesized => saida.push_str(&format!("A{i}")),
+            }
+        });
+        saida
+    }
+
+    /// Expressão com os nomes das matrizes (`names[i - 1]` é o nome de Aᵢ)
+    /// no lugar das dimensões, como `X∙(W∙b)`.
+    pub fn render_named<S: AsRef<str>>(&self, names: &[S]) -> String {
+        self.render_named_in(names, Notation::Unicode)
+    }
+
+    /// Como [`render_named`](Self::render_named), com o operador e os
+    /// parênteses de `notation`: `X * (W * b)` em `Ascii`.
+    pub fn render_named_in<S: AsRef<str>>(&self, names: &[S], notation: Notation) -> String {
+        let mut saida = String::new();
+        self.write(notation, true, &mut saida, &|i, saida: &mut String| saida.push_str(names[i - 1].as_ref()));
+        saida
+    }
+
+    // Escreve a expressão em `saida` com os símbolos de `notation`; `folha`
+    // escreve o nome da matriz Aᵢ
+    fn write(&self, notation: Notation, raiz: bool, saida: &mut String, folha: &impl Fn(usize, &mut String)) {
+        match self {
+            ParenTree::Leaf(i) => folha(*i, saida),
+            ParenTree::Product(esquerda, direita) => {
+                let (abre, operador, fecha) = notation.simbolos();
+                // Só a notação totalmente parentizada envolve o produto externo
+                let parenteses = !raiz || notation == Notation::FullyParenthesized;
+                if parenteses {
+                    saida.push_str(abre);
+                }
+                esquerda.write(notation, false, saida, folha);
+                saida.push_str(operador);
+                direita.write(notation, false, saida, folha);
+                if parenteses {
+                    saida.push_str(fecha);
+                }
+            }
+        }
+    }
+}
+
+// A destruição automática desceria um nível da pilha por nível da árvore;
+// aqui os filhos são soltos numa pilha explícita antes de cada nó cair
+impl Drop for ParenTree {
+    fn drop(&mut self) {
+        fn solta(no: &mut ParenTree, pilha: &mut Vec<ParenTree>) {
+            if let ParenTree::Product(esquerda, direita) = no {
+                pilha.push(mem::replace(&mut **esquerda, ParenTree::Leaf(0)));
+                pilha.push(mem::replace(&mut **direita, ParenTree::Leaf(0)));
+            }
+        }
+        let mut pilha = Vec::new();
+        solta(self, &mut pilha);
+        while let Some(mut no) = pilha.pop() {
+            solta(&mut no, &mut pilha);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multiplicacao_de_matrizes;
+    use crate::test_support::{CLRS, ESTOURA_U64, dimensoes};
+
+    fn clrs_tree() -> ParenTree {
+        let (_, s) = multiplicacao_de_matrizes::<usize>(&CLRS).unwrap();
+        ParenTree::from_splits(&s, 1, 6)
+    }
+
+    #[test]
+    fn test_from_splits() {
+        use ParenTree::Leaf;
+        let esperado = ParenTree::product(
+            ParenTree::product(Leaf(1), ParenTree::product(Leaf(2), Leaf(3))),
+            ParenTree::product(ParenTree::product(Leaf(4), Leaf(5)), Leaf(6)),
+        );
+        assert_eq!(clrs_tree(), esperado);
+        assert_eq!(clrs_tree().range(), (1, 6));
+    }
+
+    #[test]
+    fn test_renderers() {
+        let arvore = clrs_tree();
+        assert_eq!(
+            arvore.render(&CLRS, Notation::Unicode),
+            "(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙((A₅ₓ₁₀∙A₁₀ₓ₂₀)∙A₂₀ₓ₂₅)"
+        );
+        assert_eq!(
+            arvore.render(&CLRS, Notation::Ascii),
+            "(A_30x35 * (A_35x15 * A_15x5)) * ((A_5x10 * A_10x20) * A_20x25)"
+        );
+        assert_eq!(
+            arvore.render(&CLRS, Notation::FullyParenthesized),
+            "((A1(A2A3))((A4A5)A6))"
+        );
+        assert_eq!(
+            ParenTree::product(ParenTree::Leaf(1), ParenTree::Leaf(2)).render(&CLRS, Notation::Latex),
+            "A_{30 \\times 35} \\cdot A_{35 \\times 15}"
+        );
+        assert_eq!(ParenTree::Leaf(3).render(&CLRS, Notation::Unicode), "A₁₅ₓ₅");
+        assert_eq!(ParenTree::Leaf(3).render(&CLRS, Notation::FullyParenthesized), "A3");
+
+        let nomes = ["A", "B", "C", "D", "E", "F"];
+        assert_eq!(arvore.render_named(&nomes), "(A∙(B∙C))∙((D∙E)∙F)");
+        assert_eq!(arvore.render_named_in(&nomes, Notation::Ascii), "(A * (B * C)) * ((D * E) * F)");
+        assert_eq!(arvore.render_named_in(&nomes, Notation::FullyParenthesized), "((A(BC))((DE)F))");
+    }
+
+    #[test]
+    fn test_tree_cost_matches_table() {
+        // Dimensões pseudoaleatórias de várias cadeias
+        let mut estado: u64 = 12345;
+        for n in 1..=25 {
+            let p = dimensoes(&mut estado, n, 50);
+            let (m, s) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+            let arvore = ParenTree::from_splits(&s, 1, n);
+            assert_eq!(arvore.cost::<u64>(&p), m.get(1, n), "p = {:?}", p);
+        }
+    }
+
+    #[test]
+    fn test_all_optimal() {
+        let (m, _) = multiplicacao_de_matrizes::<usize>(&CLRS).unwrap();
+        assert_eq!(ParenTree::all_optimal(&CLRS, &m, 1, 6), vec![clrs_tree()]);
+
+        // Com matrizes quadradas iguais toda associação é ótima: C₃ = 5 para 4 matrizes
+        let p = [2, 2, 2, 2, 2];
+        let (m, _) = multiplicacao_de_matrizes::<usize>(&p).unwrap();
+        let todas: Vec<String> = ParenTree::all_optimal(&p, &m, 1, 4)
+            .iter()
+            .map(|a| a.render(&p, Notation::FullyParenthesized))
+            .collect();
+        assert_eq!(
+            todas,
+            ["(A1(A2(A3A4)))", "(A1((A2A3)A4))", "((A1A2)(A3A4))", "((A1(A2A3))A4)", "(((A1A2)A3)A4)"]
+        );
+    }
+
+    #[test]
+    fn test_tree_cost_overflow() {
+        let b = ESTOURA_U64;
+        let arvore = ParenTree::product(ParenTree::Leaf(1), ParenTree::Leaf(2));
+        assert_eq!(arvore.cost::<u64>(&[b, b, b]), None);
+        assert_eq!(arvore.cost::<u128>(&[b, b, b]), Some(1 << 66));
+    }
+}
@@ -0,0 +1,108 @@
+//! Triangulação de peso mínimo de um polígono convexo como instância de
+//! [`interval`](crate::interval).
+//!
+//! Com os vértices v₀…vₙ, os elementos do intervalo são os lados vᵢ₋₁vᵢ: o
+//! intervalo i..=j é o polígono vᵢ₋₁…vⱼ, e dividi-lo em k é escolher o
+//! triângulo vᵢ₋₁vₖvⱼ sobre a diagonal vᵢ₋₁vⱼ. Quando o peso do triângulo é o
+//! produto de pesos dos vértices, o problema é exatamente o da cadeia de
+//! matrizes com p = pesos.
+
+use crate::{Cost, IntervalProblem, PlanError, TriangularTable, interval};
+
+/// Polígono com `vertices` vértices numerados a partir de 0 e peso de cada
+/// triângulo dado por `weight(a, b, c)`, com a < b < c.
+#[derive(Debug, Clone, Copy)]
+pub struct Triangulation<F> {
+    pub vertices: usize,
+    pub weight: F,
+}
+
+impl<C: Cost, F: Fn(usize, usize, usize) -> Option<C>> IntervalProblem<C> for Triangulation<F> {
+    type Info = ();
+
+    fn size(&self) -> usize { // Sem vértices não há lados
+        self.vertices.saturating_sub(1)
+    }
+
+    fn info(&self, _: usize) {}
+
+    fn base(&self, _: usize) -> Option<C> { // Um lado sozinho não tem triângulo
+        Some(C::ZERO)
+    }
+
+    fn combine(&self, _: usize, _: usize, _: (), _: ()) {}
+
+    fn split(&self, i: usize, k: usize, j: usize, _: (), _: ()) -> Option<C> {
+        (self.weight)(i - 1, k, j)
+    }
+}
+
+/// Peso mínimo de uma triangulação do polígono e os triângulos dela, cada um
+/// com os vértices em ordem crescente. Com menos de 3 vértices o erro é
+/// [`PlanError::TooFewDimensions`].
+pub fn min_weight_triangulation<C, F>(vertices: usize, weight: F) -> Result<(C, Vec<[usize; 3]>), PlanError>
+where
+    C: Cost,
+    F: Fn(usize, usize, usize) -> Option<C>,
+{
+    if vertices < 3 {
+        return Err(PlanError::TooFewDimensions);
+    }
+    let n = vertices - 1;
+    let (m, s) = interval::solve::<C, _>(&Triangulation { vertices, weight })?;
+
+    fn triangulos(s: &TriangularTable<usize>, i: usize, j: usize, saida: &mut Vec<[usize; 3]>) {
+        if i == j {
+            return;
+        }
+        let k = s.get(i, j).unwrap();
+        saida.push([i - 1, k, j]);
+        triangulos(s, i, k, saida);
+        triangulos(s, k + 1, j, saida);
+    }
+    let mut saida = Vec::with_capacity(vertices - 2);
+    triangulos(&s, 1, n, &mut saida);
+    Ok((m.get(1, n).unwrap(), saida))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multiplicacao_de_matrizes;
+    use crate::test_support::CLRS;
+
+    #[test]
+    fn test_vertex_products_match_matrix_chain() {
+        let pesos = CLRS;
+        let (custo, triangulos) =
+            min_weight_triangulation::<u64, _>(pesos.len(), |a, b, c| Some((pesos[a] * pesos[b] * pesos[c]) as u64)).unwrap();
+        let (m, _) = multiplicacao_de_matrizes::<u64>(&pesos).unwrap();
+        assert_eq!(Some(custo), m.get(1, 6));
+        assert_eq!(triangulos.len(), 5);
+    }
+
+    #[test]
+    fn test_area_is_the_same_for_every_triangulation() {
+        // Hexágono convexo com coordenadas inteiras; o peso é o dobro da área,
+        // então toda triangulação pesa o dobro da área do hexágono
+        let pontos: [(i64, i64); 6] = [(0, 0), (4, 0), (6, 3), (4, 6), (0, 6), (-2, 3)];
+        let area_dupla = |a: usize, b: usize, c: usize| {
+            let ((xa, ya), (xb, yb), (xc, yc)) = (pontos[a], pontos[b], pontos[c]);
+            Some(((xb - xa) * (yc - ya) - (xc - xa) * (yb - ya)).unsigned_abs())
+        };
+        let (custo, triangulos) = min_weight_triangulation::<u64, _>(6, area_dupla).unwrap();
+        assert_eq!(custo, 2 * 36);
+        assert_eq!(triangulos.len(), 4);
+        let soma: u64 = triangulos.iter().map(|&[a, b, c]| area_dupla(a, b, c).unwrap()).sum();
+        assert_eq!(soma, custo);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let (custo, triangulos) = min_weight_triangulation::<u32, _>(3, |_, _, _| Some(7)).unwrap();
+        assert_eq!((custo, triangulos), (7, vec![[0, 1, 2]]));
+        for vertices in 0..3 {
+            assert_eq!(min_weight_triangulation::<u32, _>(vertices, |_, _, _| Some(7)), Err(PlanError::TooFewDimensions));
+        }
+    }
+}
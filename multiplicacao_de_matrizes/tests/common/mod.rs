@@ -0,0 +1,19 @@
+//! Dados e geradores compartilhados pelos testes de integração e benchmarks.
+// Cada arquivo que inclui este módulo usa só parte dele
+#![allow(dead_code)]
+
+/// Cadeia da seção 15.2 do CLRS, de custo ótimo 15125.
+pub const CLRS: [usize; 7] = [30, 35, 15, 5, 10, 20, 25];
+
+/// Próximo valor do gerador xorshift64; `estado` não pode ser zero.
+pub fn proximo(estado: &mut u64) -> u64 {
+    *estado ^= *estado << 13;
+    *estado ^= *estado >> 7;
+    *estado ^= *estado << 17;
+    *estado
+}
+
+/// As n + 1 dimensões de uma cadeia pseudoaleatória, todas em 1..=maximo.
+pub fn dimensoes(estado: &mut u64, n: usize, maximo: usize) -> Vec<usize> {
+    (0..=n).map(|_| (proximo(estado) % maximo as u64 + 1) as usize).collect()
+}
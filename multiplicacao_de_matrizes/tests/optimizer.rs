@@ -0,0 +1,144 @@
+mod common;
+
+use common::{CLRS, dimensoes};
+use multiplicacao_de_matrizes::{Notation, ParenTree, TriangularTable, encontra_associacoes, format_index, multiplicacao_de_matrizes, plan};
+
+// Todas as associações de Aᵢ…Aⱼ: são C(j-i) delas, o número de Catalan
+fn todas_as_arvores(i: usize, j: usize) -> Vec<ParenTree> {
+    if i == j {
+        return vec![ParenTree::Leaf(i)];
+    }
+    let mut arvores = Vec::new();
+    for k in i..j {
+        let direitas = todas_as_arvores(k + 1, j);
+        for esquerda in todas_as_arvores(i, k) {
+            for direita in &direitas {
+                arvores.push(ParenTree::product(esquerda.clone(), direita.clone()));
+            }
+        }
+    }
+    arvores
+}
+
+// Tabela s que reconstrói exatamente a árvore dada
+fn divisoes(arvore: &ParenTree, s: &mut TriangularTable<usize>) {
+    if let ParenTree::Product(esquerda, direita) = arvore {
+        let (i, k) = esquerda.range();
+        let (_, j) = direita.range();
+        s.set(i, j, k);
+        divisoes(esquerda, s);
+        divisoes(direita, s);
+    }
+}
+
+// Monta a expressão do jeito do programa original: matrizes intercaladas com
+// "∙", um espaço no fim e os parênteses acrescentados por encontra_associacoes
+fn expressao_original(p: &[usize], s: &TriangularTable<usize>) -> String {
+    let n = p.len() - 1;
+    let mut saida = vec![String::new()];
+    for i in 0..n {
+        saida.push(format!("A{}ₓ{}", format_index(p[i]), format_index(p[i + 1])));
+        saida.push(if i != n - 1 { "∙".to_owned() } else { " ".to_owned() });
+    }
+    encontra_associacoes(&mut saida, s, 1, n);
+    saida.concat()
+}
+
+#[test]
+fn test_textbook_chains() {
+    // CLRS, seção 15.2
+    let p = CLRS;
+    let plano = plan(&p).unwrap();
+    assert_eq!(plano.cost, 15125);
+    assert_eq!(plano.tree.render(&p, Notation::FullyParenthesized), "((A1(A2A3))((A4A5)A6))");
+
+    // CLRS, exercício 15.2-1
+    let p = [5, 10, 3, 12, 5, 50, 6];
+    let plano = plan(&p).unwrap();
+    assert_eq!(plano.cost, 2010);
+    assert_eq!(plano.tree.render(&p, Notation::FullyParenthesized), "((A1A2)((A3A4)(A5A6)))");
+
+    // 10×30, 30×5, 5×60: (A₁A₂)A₃ custa 4500 e A₁(A₂A₃) custa 27000
+    let plano = plan(&[10, 30, 5, 60]).unwrap();
+    assert_eq!(plano.cost, 4500);
+    assert_eq!(plano.tree.render(&[10, 30, 5, 60], Notation::FullyParenthesized), "((A1A2)A3)");
+}
+
+#[test]
+fn test_catalan_counts() {
+    let catalan = [1, 1, 2, 5, 14, 42, 132, 429];
+    for (n, &esperado) in (1..=8).zip(&catalan) {
+        assert_eq!(todas_as_arvores(1, n).len(), esperado);
+    }
+}
+
+#[test]
+fn test_against_every_tree() {
+    let mut estado: u64 = 0xA076_1D64_78BD_642F;
+    for n in 1..=8 {
+        let arvores = todas_as_arvores(1, n);
+        for _ in 0..20 {
+            let p = dimensoes(&mut estado, n, 40);
+            let minimo = arvores.iter().map(|a| a.cost::<u64>(&p).unwrap()).min().unwrap();
+            let (m, s) = multiplicacao_de_matrizes::<u64>(&p).unwrap();
+            assert_eq!(m.get(1, n), Some(minimo), "p = {p:?}");
+            assert_eq!(ParenTree::from_splits(&s, 1, n).cost::<u64>(&p), Some(minimo), "p = {p:?}");
+        }
+    }
+}
+
+#[test]
+fn test_rendered_snapshots() {
+    let p = [30, 35, 15, 5, 10, 20, 25, 40, 12];
+    let esperado = [
+        ("A₃₀ₓ₃₅", "A1"),
+        ("A₃₀ₓ₃₅∙A₃₅ₓ₁₅", "(A1A2)"),
+        ("A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅)", "(A1(A2A3))"),
+        ("(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙A₅ₓ₁₀", "((A1(A2A3))A4)"),
+        ("(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙(A₅ₓ₁₀∙A₁₀ₓ₂₀)", "((A1(A2A3))(A4A5))"),
+        ("(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙((A₅ₓ₁₀∙A₁₀ₓ₂₀)∙A₂₀ₓ₂₅)", "((A1(A2A3))((A4A5)A6))"),
+        ("(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙(((A₅ₓ₁₀∙A₁₀ₓ₂₀)∙A₂₀ₓ₂₅)∙A₂₅ₓ₄₀)", "((A1(A2A3))(((A4A5)A6)A7))"),
+        ("(A₃₀ₓ₃₅∙(A₃₅ₓ₁₅∙A₁₅ₓ₅))∙((((A₅ₓ₁₀∙A₁₀ₓ₂₀)∙A₂₀ₓ₂₅)∙A₂₅ₓ₄₀)∙A₄₀ₓ₁₂)", "((A1(A2A3))((((A4A5)A6)A7)A8))"),
+    ];
+    for (n, (unicode, parentizada)) in (1..=8).zip(esperado) {
+        let p = &p[..=n];
+        let arvore = plan(p).unwrap().tree;
+        assert_eq!(arvore.render(p, Notation::Unicode), unicode, "n = {n}");
+        assert_eq!(arvore.render(p, Notation::FullyParenthesized), parentizada, "n = {n}");
+    }
+}
+
+#[test]
+fn test_encontra_associacoes_matches_render() {
+    // Todas as formas de árvore até 7 matrizes passam por todos os ramos:
+    // divisão no meio, logo depois da primeira matriz e logo antes da última
+    let mut estado: u64 = 0x5851_F42D_4C95_7F2D;
+    for n in 1..=7 {
+        let p = dimensoes(&mut estado, n, 40);
+        for arvore in todas_as_arvores(1, n) {
+            let mut s = TriangularTable::new(n);
+            divisoes(&arvore, &mut s);
+            assert_eq!(expressao_original(&p, &s), format!("{} ", arvore.render(&p, Notation::Unicode)));
+        }
+    }
+}
+
+#[test]
+fn test_encontra_associacoes_branches() {
+    let p = [2, 3, 4, 5, 6];
+    let caso = |expr: &str| {
+        let mut s = TriangularTable::new(4);
+        divisoes(&ParenTree::parse(expr).unwrap(), &mut s);
+        expressao_original(&p, &s)
+    };
+    // Os dois lados com mais de uma matriz, o da esquerda com só duas
+    assert_eq!(caso("((A1A2)(A3A4))"), "(A₂ₓ₃∙A₃ₓ₄)∙(A₄ₓ₅∙A₅ₓ₆) ");
+    // Só o lado direito com mais de uma matriz (sep_i = l)
+    assert_eq!(caso("(A1((A2A3)A4))"), "A₂ₓ₃∙((A₃ₓ₄∙A₄ₓ₅)∙A₅ₓ₆) ");
+    // Só o lado esquerdo com mais de uma matriz (sep_i = r - 1)
+    assert_eq!(caso("((A1(A2A3))A4)"), "(A₂ₓ₃∙(A₃ₓ₄∙A₄ₓ₅))∙A₅ₓ₆ ");
+    // Duas matrizes: nenhum parêntese
+    let mut s = TriangularTable::new(2);
+    s.set(1, 2, 1);
+    assert_eq!(expressao_original(&p[..3], &s), "A₂ₓ₃∙A₃ₓ₄ ");
+}
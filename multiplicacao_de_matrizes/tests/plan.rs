@@ -0,0 +1,150 @@
+mod common;
+
+use common::{CLRS, proximo};
+use multiplicacao_de_matrizes::{ChainPlan, CostOverflow, Lang, MemoryPlan, Notation, ParenTree, PlanError, pareto_plans, parse_dimensions, plan, plan_within};
+
+#[test]
+fn test_clrs_plan() {
+    let p = CLRS;
+    let ChainPlan { cost, tree } = plan(&p).unwrap();
+    assert_eq!(cost, 15125);
+    assert_eq!(tree.render(&p, Notation::FullyParenthesized), "((A1(A2A3))((A4A5)A6))");
+    assert_eq!(tree.cost::<u128>(&p), Some(cost));
+}
+
+#[test]
+fn test_single_matrix() {
+    let plano = plan(&[4, 7]).unwrap();
+    assert_eq!(plano, ChainPlan { cost: 0, tree: ParenTree::Leaf(1) });
+}
+
+#[test]
+fn test_from_typed_input() {
+    let p = parse_dimensions("10x20, 20x5, 5x30").unwrap();
+    let plano = plan(&p).unwrap();
+    // (A₁A₂)A₃ = 10·20·5 + 10·5·30 = 2500, contra 20·5·30 + 10·20·30 = 9000
+    assert_eq!(plano.cost, 2500);
+    assert_eq!(plano.tree.render(&p, Notation::Ascii), "(A_10x20 * A_20x5) * A_5x30");
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(plan(&[]), Err(PlanError::TooFewDimensions));
+    assert_eq!(plan(&[3]), Err(PlanError::TooFewDimensions));
+
+    // Cada produto custa (2⁴³)³ = 2¹²⁹, que não cabe em u128
+    let b = 1 << 43;
+    assert_eq!(plan(&[b, b, b, b]), Err(PlanError::Overflow(CostOverflow::of::<u128>())));
+}
+
+#[test]
+fn test_memory_errors() {
+    assert_eq!(pareto_plans(&[]), Err(PlanError::TooFewDimensions));
+    assert_eq!(plan_within(&[3], 10), Err(PlanError::TooFewDimensions));
+
+    // O único produto custa (2⁶³)³ = 2¹⁸⁹, que não cabe em u128
+    let b = 1 << 63;
+    let estouro = PlanError::Overflow(CostOverflow::of::<u128>());
+    assert_eq!(pareto_plans(&[b; 3]), Err(estouro.clone()));
+    assert_eq!(plan_within(&[b; 3], u128::MAX), Err(estouro));
+}
+
+// Todas as associações de Aᵢ…Aⱼ
+fn todas_as_arvores(i: usize, j: usize) -> Vec<ParenTree> {
+    if i == j {
+        return vec![ParenTree::Leaf(i)];
+    }
+    let mut arvores = Vec::new();
+    for k in i..j {
+        let direitas = todas_as_arvores(k + 1, j);
+        for esquerda in todas_as_arvores(i, k) {
+            for direita in &direitas {
+                arvores.push(ParenTree::product(esquerda.clone(), direita.clone()));
+            }
+        }
+    }
+    arvores
+}
+
+// Maior produto calculado pela árvore, em elementos
+fn pico(arvore: &ParenTree, p: &[usize]) -> u128 {
+    match arvore {
+        ParenTree::Leaf(_) => 0,
+        ParenTree::Product(esquerda, direita) => {
+            let (i, j) = arvore.range();
+            (p[i - 1] as u128 * p[j] as u128).max(pico(esquerda, p)).max(pico(direita, p))
+        }
+    }
+}
+
+fn render(plano: &MemoryPlan, p: &[usize]) -> String {
+    plano.tree.render(p, Notation::FullyParenthesized)
+}
+
+#[test]
+fn test_pareto_frontier() {
+    let p = [1, 2, 100, 2, 50, 20];
+    let planos = pareto_plans(&p).unwrap();
+    let pontos: Vec<(u128, u128, String)> =
+        planos.iter().map(|plano| (plano.cost, plano.peak, render(plano, &p))).collect();
+    assert_eq!(
+        pontos,
+        [
+            (1500, 100, "((((A1A2)A3)A4)A5)".to_owned()),
+            (1504, 50, "(((A1(A2A3))A4)A5)".to_owned()),
+            (2444, 40, "((A1(A2A3))(A4A5))".to_owned()),
+        ]
+    );
+    // A ponta mais barata é o plano de sempre
+    assert_eq!(planos[0].cost, plan(&p).unwrap().cost);
+    for plano in &planos {
+        assert_eq!(plano.tree.cost::<u128>(&p), Some(plano.cost));
+        assert_eq!(pico(&plano.tree, &p), plano.peak);
+    }
+}
+
+#[test]
+fn test_plan_within() {
+    let p = [1, 2, 100, 2, 50, 20];
+    assert_eq!(plan_within(&p, u128::MAX).unwrap().cost, 1500);
+    assert_eq!(plan_within(&p, 99).unwrap().cost, 1504);
+    let enxuto = plan_within(&p, 40).unwrap();
+    assert_eq!((enxuto.cost, enxuto.peak), (2444, 40));
+    let erro = plan_within(&p, 39).unwrap_err();
+    assert_eq!(erro, PlanError::MemoryCeiling { ceiling: 39, minimum: 40 });
+    assert_eq!(erro.message(Lang::En), "No order fits in 39 elements of memory; the leanest one needs 40");
+
+    assert_eq!(plan_within(&[4, 7], 0).unwrap(), MemoryPlan { cost: 0, peak: 0, tree: ParenTree::Leaf(1) });
+    assert_eq!(plan_within(&[5], 10), Err(PlanError::TooFewDimensions));
+}
+
+#[test]
+fn test_pareto_against_every_tree() {
+    let mut estado: u64 = 0x2545_F491_4F6C_DD1D;
+    for n in 1..=7 {
+        for _ in 0..15 {
+            let p: Vec<usize> = (0..=n)
+                .map(|_| [1, 2, 5, 10, 20, 50, 100][(proximo(&mut estado) % 7) as usize])
+                .collect();
+            // Fronteira por força bruta: pontos (custo, pico) que nenhum outro domina
+            let mut pontos: Vec<(u128, u128)> = todas_as_arvores(1, n)
+                .iter()
+                .map(|arvore| (arvore.cost::<u128>(&p).unwrap(), pico(arvore, &p)))
+                .collect();
+            pontos.sort();
+            let mut esperado: Vec<(u128, u128)> = Vec::new();
+            for (custo, pico) in pontos {
+                if esperado.last().is_none_or(|&(_, anterior)| pico < anterior) {
+                    esperado.push((custo, pico));
+                }
+            }
+            let planos = pareto_plans(&p).unwrap();
+            let obtido: Vec<(u128, u128)> = planos.iter().map(|plano| (plano.cost, plano.peak)).collect();
+            assert_eq!(obtido, esperado, "p = {p:?}");
+            for plano in &planos {
+                assert_eq!((plano.tree.cost::<u128>(&p), pico(&plano.tree, &p)), (Some(plano.cost), plano.peak));
+            }
+        }
+    }
+}
+
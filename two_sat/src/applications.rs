@@ -0,0 +1,209 @@
+//! Reduções prontas de problemas concretos para 2-SAT. Cada função recebe a
+//! descrição do problema no seu próprio domínio e devolve a resposta também
+//! no domínio, escondendo as chamadas a `add_clause`.
+
+use crate::{Literal, TwoSATSolver};
+
+/// Intervalo semiaberto `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Interval {
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// Uma tarefa que pode ser executada em um de dois horários.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Job {
+    pub options: [Interval; 2],
+}
+
+/// Retângulo alinhado aos eixos com canto inferior esquerdo em `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    // Retângulos que apenas se tocam na borda não se sobrepõem
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// Um rótulo do mapa com duas posições candidatas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Label {
+    pub positions: [Rect; 2],
+}
+
+/// Restrições de 2-coloração: pares de vértices que devem ter a mesma cor e
+/// pares que devem ter cores diferentes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColoringConstraints {
+    pub num_vertices: usize,
+    pub same: Vec<(usize, usize)>,
+    pub different: Vec<(usize, usize)>,
+}
+
+// A variável `i` verdadeira significa que o item `i` usa a opção 0
+fn choice(item: usize, option: usize) -> Literal {
+    Literal::new(item, option == 0)
+}
+
+// Proíbe que `a` e `b` sejam verdadeiros ao mesmo tempo: (¬a ∨ ¬b)
+fn forbid(solver: &mut TwoSATSolver, a: Literal, b: Literal) {
+    solver.add_clause(!a.value, a.var, !b.value, b.var);
+}
+
+// Escolhe uma de duas opções para cada um dos `num_items` itens de forma que
+// nenhum par de escolhas esteja em conflito
+fn choose_without_conflicts<F>(num_items: usize, conflict: F) -> Option<Vec<usize>>
+where
+    F: Fn(usize, usize, usize, usize) -> bool,
+{
+    let mut solver = TwoSATSolver::new(num_items);
+    for i in 0..num_items {
+        for j in i + 1..num_items {
+            for a in 0..2 {
+                for b in 0..2 {
+                    if conflict(i, a, j, b) {
+                        forbid(&mut solver, choice(i, a), choice(j, b));
+                    }
+                }
+            }
+        }
+    }
+
+    let assignment = solver.solve()?;
+    Some(assignment.into_iter().map(|first| if first { 0 } else { 1 }).collect())
+}
+
+/// Escolhe um horário para cada tarefa de forma que nenhuma se sobreponha.
+/// Devolve, para cada tarefa, o índice (0 ou 1) da opção escolhida.
+pub fn schedule_jobs(jobs: &[Job]) -> Option<Vec<usize>> {
+    choose_without_conflicts(jobs.len(), |i, a, j, b| {
+        jobs[i].options[a].overlaps(&jobs[j].options[b])
+    })
+}
+
+/// Posiciona os rótulos sem sobreposição. Devolve, para cada rótulo, o índice
+/// (0 ou 1) da posição escolhida.
+pub fn place_labels(labels: &[Label]) -> Option<Vec<usize>> {
+    choose_without_conflicts(labels.len(), |i, a, j, b| {
+        labels[i].positions[a].overlaps(&labels[j].positions[b])
+    })
+}
+
+/// Pinta os vértices com as cores 0 e 1 respeitando as restrições.
+pub fn two_color(constraints: &ColoringConstraints) -> Option<Vec<usize>> {
+    let mut solver = TwoSATSolver::new(constraints.num_vertices);
+    for &(u, v) in &constraints.same {
+        // u ↔ v: (u ∨ ¬v) ∧ (¬u ∨ v)
+        solver.add_clause(true, u, false, v);
+        solver.add_clause(false, u, true, v);
+    }
+    for &(u, v) in &constraints.different {
+        // u ⊕ v: (u ∨ v) ∧ (¬u ∨ ¬v)
+        solver.add_clause(true, u, true, v);
+        solver.add_clause(false, u, false, v);
+    }
+
+    let assignment = solver.solve()?;
+    Some(assignment.into_iter().map(usize::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: u64, end: u64) -> Interval {
+        Interval { start, end }
+    }
+
+    fn rect(x: f64, y: f64) -> Rect {
+        Rect { x, y, width: 2.0, height: 1.0 }
+    }
+
+    #[test]
+    fn test_schedule_jobs() {
+        let jobs = [
+            Job { options: [interval(0, 2), interval(4, 6)] },
+            Job { options: [interval(1, 3), interval(5, 7)] },
+            Job { options: [interval(0, 1), interval(2, 4)] },
+        ];
+
+        let choices = schedule_jobs(&jobs).expect("Existe um horário válido");
+        for i in 0..jobs.len() {
+            for j in i + 1..jobs.len() {
+                let a = jobs[i].options[choices[i]];
+                let b = jobs[j].options[choices[j]];
+                assert!(!a.overlaps(&b), "Tarefas {} e {} se sobrepõem", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedule_jobs_impossible() {
+        // Três tarefas disputando os mesmos dois horários
+        let job = Job { options: [interval(0, 2), interval(2, 4)] };
+        assert_eq!(schedule_jobs(&[job, job, job]), None);
+    }
+
+    #[test]
+    fn test_place_labels() {
+        // O rótulo do meio só cabe se os vizinhos forem para fora
+        let labels = [
+            Label { positions: [rect(1.0, 0.0), rect(-2.0, 0.0)] },
+            Label { positions: [rect(2.0, 0.0), rect(2.0, 0.5)] },
+            Label { positions: [rect(3.0, 0.0), rect(5.0, 0.0)] },
+        ];
+
+        let choices = place_labels(&labels).expect("Existe um posicionamento válido");
+        assert_eq!(choices[0], 1);
+        assert_eq!(choices[2], 1);
+        for i in 0..labels.len() {
+            for j in i + 1..labels.len() {
+                let a = labels[i].positions[choices[i]];
+                let b = labels[j].positions[choices[j]];
+                assert!(!a.overlaps(&b), "Rótulos {} e {} se sobrepõem", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_color() {
+        let constraints = ColoringConstraints {
+            num_vertices: 4,
+            same: vec![(0, 2)],
+            different: vec![(0, 1), (1, 3)],
+        };
+
+        let colors = two_color(&constraints).expect("As restrições são consistentes");
+        assert_eq!(colors[0], colors[2]);
+        assert_ne!(colors[0], colors[1]);
+        assert_ne!(colors[1], colors[3]);
+    }
+
+    #[test]
+    fn test_two_color_odd_cycle() {
+        // Um ciclo ímpar de "cores diferentes" não é 2-colorível
+        let constraints = ColoringConstraints {
+            num_vertices: 3,
+            same: vec![],
+            different: vec![(0, 1), (1, 2), (2, 0)],
+        };
+        assert_eq!(two_color(&constraints), None);
+    }
+}
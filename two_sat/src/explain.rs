@@ -0,0 +1,172 @@
+//! Explicações para o valor de uma variável no modelo: um literal é forçado
+//! exatamente quando existe um caminho `¬l ⇝ l` no grafo de implicações, e
+//! cada aresta desse caminho vem de uma cláusula.
+
+use std::fmt;
+
+use crate::{ClauseId, Literal, Solution, TwoSATSolver, UnsatProof};
+
+/// Uma aresta `from → to` do grafo de implicações e a cláusula que a criou.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImplicationStep {
+    pub from: Literal,
+    pub to: Literal,
+    pub clause: ClauseId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Explanation {
+    /// `literal` vale em todo modelo: supor `¬literal` leva, pela cadeia de
+    /// implicações, ao próprio `literal`.
+    Forced { literal: Literal, chain: Vec<ImplicationStep> },
+    /// Os dois valores da variável são consistentes com o resto do modelo.
+    Free,
+    /// A fórmula não tem modelo.
+    Unsatisfiable(UnsatProof),
+}
+
+/// Variável pedida em [`TwoSATSolver::explain`] que o solver não tem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariableOutOfRange {
+    pub var: usize,
+    pub num_vars: usize,
+}
+
+impl fmt::Display for VariableOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variável x{} fora do intervalo (o solver tem {} variáveis)", self.var, self.num_vars)
+    }
+}
+
+impl std::error::Error for VariableOutOfRange {}
+
+impl TwoSATSolver {
+    // Explica por que `var` tem o valor que tem nos modelos da fórmula
+    pub fn explain(&self, var: usize) -> Result<Explanation, VariableOutOfRange> {
+        if var >= self.n {
+            return Err(VariableOutOfRange { var, num_vars: self.n });
+        }
+        if let Solution::Unsatisfiable(proof) = self.solve_with_proof() {
+            return Ok(Explanation::Unsatisfiable(proof));
+        }
+
+        // Numa fórmula satisfatível no máximo um dos dois caminhos existe
+        for literal in [Literal::new(var, true), Literal::new(var, false)] {
+            if let Some(path) = self.implication_path(literal.negate().index(), literal.index()) {
+                return Ok(Explanation::Forced { literal, chain: self.annotate(&path) });
+            }
+        }
+        Ok(Explanation::Free)
+    }
+
+    // Associa cada aresta do caminho à primeira cláusula que a gerou, usando
+    // `graph_clauses`, que anda em paralelo com as listas de adjacência
+    fn annotate(&self, path: &[usize]) -> Vec<ImplicationStep> {
+        path.windows(2)
+            .map(|edge| {
+                let (u, v) = (edge[0], edge[1]);
+                let position = self.graph[u].iter()
+                    .position(|&w| w == v)
+                    .expect("o caminho só usa arestas do grafo");
+                ImplicationStep {
+                    from: Literal::from_index(u),
+                    to: Literal::from_index(v),
+                    clause: ClauseId(self.graph_clauses[u][position]),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forced_chain() {
+        // (x0 ∨ x0) ∧ (¬x0 ∨ x1) ∧ (¬x1 ∨ x2): x0 é verdadeiro e arrasta x1 e x2
+        let mut solver = TwoSATSolver::new(4);
+        let unit = solver.add_clause(true, 0, true, 0);
+        let first = solver.add_clause(false, 0, true, 1);
+        let second = solver.add_clause(false, 1, true, 2);
+
+        let Explanation::Forced { literal, chain } = solver.explain(2).unwrap() else {
+            panic!("x2 deveria ser forçado");
+        };
+        assert_eq!(literal, Literal::new(2, true));
+
+        // ¬x2 → ¬x1 → ¬x0 → x0 → x1 → x2
+        let clauses: Vec<_> = chain.iter().map(|step| step.clause).collect();
+        assert_eq!(clauses, vec![second, first, unit, first, second]);
+        assert_eq!(chain.first().unwrap().from, Literal::new(2, false));
+        assert_eq!(chain.last().unwrap().to, Literal::new(2, true));
+        for step in chain.windows(2) {
+            assert_eq!(step[0].to, step[1].from);
+        }
+    }
+
+    #[test]
+    fn test_forced_false() {
+        // (¬x0 ∨ x1) ∧ (¬x0 ∨ ¬x1): x0 implica x1 e ¬x1
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(false, 0, true, 1);
+        solver.add_clause(false, 0, false, 1);
+
+        match solver.explain(0).unwrap() {
+            Explanation::Forced { literal, chain } => {
+                assert_eq!(literal, Literal::new(0, false));
+                assert_eq!(chain.len(), 2);
+            }
+            other => panic!("Explicação inesperada: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_free_variable() {
+        let mut solver = TwoSATSolver::new(3);
+        solver.add_clause(true, 0, true, 1);
+        solver.add_clause(true, 2, true, 2);
+
+        assert_eq!(solver.explain(0), Ok(Explanation::Free));
+        assert_eq!(solver.explain(1), Ok(Explanation::Free));
+        assert!(matches!(solver.explain(2), Ok(Explanation::Forced { .. })));
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        let mut solver = TwoSATSolver::new(1);
+        solver.add_clause(true, 0, true, 0);
+        solver.add_clause(false, 0, false, 0);
+
+        assert!(matches!(solver.explain(0), Ok(Explanation::Unsatisfiable(_))));
+    }
+
+    #[test]
+    fn test_variable_out_of_range() {
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(true, 0, true, 1);
+
+        assert_eq!(solver.explain(2), Err(VariableOutOfRange { var: 2, num_vars: 2 }));
+        assert_eq!(
+            solver.explain(2).unwrap_err().to_string(),
+            "variável x2 fora do intervalo (o solver tem 2 variáveis)"
+        );
+    }
+
+    #[test]
+    fn test_chain_after_removal() {
+        // Remover a primeira cláusula que gera ¬x0 → x1 deixa a segunda como origem
+        let mut solver = TwoSATSolver::new(2);
+        let removed = solver.add_clause(true, 0, true, 1);
+        let kept = solver.add_clause(true, 1, true, 0);
+        let unit = solver.add_clause(false, 0, false, 0);
+        solver.remove_clause(removed);
+
+        let Ok(Explanation::Forced { literal, chain }) = solver.explain(1) else {
+            panic!("x1 deveria ser forçado");
+        };
+        assert_eq!(literal, Literal::new(1, true));
+        let clauses: Vec<_> = chain.iter().map(|step| step.clause).collect();
+        assert_eq!(clauses, vec![kept, unit, kept]);
+    }
+}
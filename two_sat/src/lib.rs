@@ -0,0 +1,556 @@
+//! Resolvedor de 2-SAT pelo grafo de implicações: a fórmula é satisfatível se,
+//! e somente se, nenhuma variável está na mesma componente fortemente conexa
+//! que a sua negação.
+
+use std::collections::VecDeque;
+
+pub mod applications;
+pub mod explain;
+pub mod quantified;
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+/// Um literal: a variável `var` com o valor `value` (`false` representa `¬var`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Literal {
+    pub var: usize,
+    pub value: bool,
+}
+
+impl Literal {
+    pub fn new(var: usize, value: bool) -> Self {
+        Literal { var, value }
+    }
+
+    // Posição do literal no grafo de implicações: 2v para v e 2v + 1 para ¬v
+    pub fn index(self) -> usize {
+        if self.value { 2 * self.var } else { 2 * self.var + 1 }
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        Literal { var: index / 2, value: index & 1 == 0 }
+    }
+
+    pub fn negate(self) -> Self {
+        Literal { var: self.var, value: !self.value }
+    }
+}
+
+/// Uma cláusula (a ∨ b).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clause {
+    pub a: Literal,
+    pub b: Literal,
+}
+
+/// Identificador de uma cláusula, devolvido por [`TwoSATSolver::add_clause`].
+/// Continua válido (e nunca é reaproveitado) depois que outras cláusulas são
+/// removidas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClauseId(pub usize);
+
+/// Certificado de insatisfatibilidade: os caminhos `x ⇝ ¬x` e `¬x ⇝ x` no
+/// grafo de implicações, que juntos obrigam `x` a ser verdadeiro e falso.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnsatProof {
+    pub var: usize,
+    pub to_negation: Vec<Literal>,
+    pub from_negation: Vec<Literal>,
+}
+
+/// Resultado de [`TwoSATSolver::solve_with_proof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Solution {
+    Satisfiable(Vec<bool>),
+    Unsatisfiable(UnsatProof),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serialization::SolverState", into = "serialization::SolverState")
+)]
+pub struct TwoSATSolver {
+    n: usize,
+    graph: Vec<Vec<usize>>,
+    transposed: Vec<Vec<usize>>,
+    // A cláusula que gerou cada aresta, em paralelo com `graph` e `transposed`
+    graph_clauses: Vec<Vec<usize>>,
+    transposed_clauses: Vec<Vec<usize>>,
+    // Cláusulas indexadas pelo `ClauseId`; as removidas viram `None`
+    clauses: Vec<Option<Clause>>,
+    names: Vec<Option<String>>,
+}
+
+impl TwoSATSolver {
+    pub fn new(num_vars: usize) -> Self {
+        let num_nodes = 2 * num_vars;
+        TwoSATSolver {
+            n: num_vars,
+            graph: vec![Vec::new(); num_nodes],
+            transposed: vec![Vec::new(); num_nodes],
+            graph_clauses: vec![Vec::new(); num_nodes],
+            transposed_clauses: vec![Vec::new(); num_nodes],
+            clauses: Vec::new(),
+            names: vec![None; num_vars],
+        }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.n
+    }
+
+    // Cláusulas ainda presentes no solver, em ordem de inserção
+    pub fn clauses(&self) -> impl Iterator<Item = (ClauseId, Clause)> + '_ {
+        self.clauses
+            .iter()
+            .enumerate()
+            .filter_map(|(id, clause)| clause.map(|clause| (ClauseId(id), clause)))
+    }
+
+    pub fn clause(&self, id: ClauseId) -> Option<Clause> {
+        self.clauses.get(id.0).copied().flatten()
+    }
+
+    pub fn set_var_name(&mut self, var: usize, name: impl Into<String>) {
+        self.names[var] = Some(name.into());
+    }
+
+    pub fn var_name(&self, var: usize) -> Option<&str> {
+        self.names[var].as_deref()
+    }
+
+    // Adiciona uma cláusula (a ∨ b) ao solver
+    pub fn add_clause(&mut self, a: bool, a_var: usize, b: bool, b_var: usize) -> ClauseId {
+        // Mapeia variáveis booleanas para literais
+        let literal_a = Literal::new(a_var, a).index();
+        let literal_b = Literal::new(b_var, b).index();
+        let not_a = literal_a ^ 1;
+        let not_b = literal_b ^ 1;
+
+        // Adiciona arestas de implicação: (¬a → b) e (¬b → a)
+        let id = self.clauses.len();
+        self.graph[not_a].push(literal_b);
+        self.graph[not_b].push(literal_a);
+        self.transposed[literal_b].push(not_a);
+        self.transposed[literal_a].push(not_b);
+        self.graph_clauses[not_a].push(id);
+        self.graph_clauses[not_b].push(id);
+        self.transposed_clauses[literal_b].push(id);
+        self.transposed_clauses[literal_a].push(id);
+
+        self.clauses.push(Some(Clause { a: Literal::new(a_var, a), b: Literal::new(b_var, b) }));
+        ClauseId(id)
+    }
+
+    // Remove uma cláusula adicionada antes, devolvendo-a, ou `None` se ela já
+    // tinha sido removida. Custa O(grau) dos quatro literais envolvidos, pois
+    // cada aresta é procurada na lista de adjacência; a próxima chamada a
+    // `solve` continua O(n + m) sobre as cláusulas restantes.
+    pub fn remove_clause(&mut self, id: ClauseId) -> Option<Clause> {
+        let clause = self.clauses.get_mut(id.0)?.take()?;
+        let literal_a = clause.a.index();
+        let literal_b = clause.b.index();
+        let not_a = literal_a ^ 1;
+        let not_b = literal_b ^ 1;
+
+        for node in [not_a, not_b] {
+            Self::remove_edge(&mut self.graph[node], &mut self.graph_clauses[node], id.0);
+        }
+        for node in [literal_b, literal_a] {
+            Self::remove_edge(&mut self.transposed[node], &mut self.transposed_clauses[node], id.0);
+        }
+        Some(clause)
+    }
+
+    // Remove da lista de adjacência a aresta gerada pela cláusula `id`. As
+    // demais arestas mantêm a ordem, para que o grafo fique igual ao de um
+    // solver montado só com as cláusulas restantes (como o restaurado de um
+    // estado serializado). Procurar pela cláusula, e não pelo vértice de
+    // destino, importa quando duas cláusulas geram a mesma aresta.
+    fn remove_edge(adjacency: &mut Vec<usize>, edge_clauses: &mut Vec<usize>, id: usize) {
+        let position = edge_clauses.iter()
+            .position(|&c| c == id)
+            .expect("toda cláusula presente tem suas arestas no grafo");
+        adjacency.remove(position);
+        edge_clauses.remove(position);
+    }
+
+    // Resolve o problema 2-SAT
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        match self.solve_with_proof() {
+            Solution::Satisfiable(assignment) => Some(assignment),
+            Solution::Unsatisfiable(_) => None,
+        }
+    }
+
+    // Resolve o problema 2-SAT e, se ele for insatisfatível, devolve o par de
+    // caminhos x ⇝ ¬x ⇝ x que prova a contradição
+    pub fn solve_with_proof(&self) -> Solution {
+        let (_, comp) = self.kosaraju();
+
+        // Verifica se alguma variável e sua negação estão na mesma SCC
+        for i in 0..self.n {
+            if comp[2 * i] == comp[2 * i + 1] {
+                let to_negation = self.implication_path(2 * i, 2 * i + 1)
+                    .expect("x e ¬x estão na mesma SCC");
+                let from_negation = self.implication_path(2 * i + 1, 2 * i)
+                    .expect("x e ¬x estão na mesma SCC");
+                return Solution::Unsatisfiable(UnsatProof {
+                    var: i,
+                    to_negation: to_negation.into_iter().map(Literal::from_index).collect(),
+                    from_negation: from_negation.into_iter().map(Literal::from_index).collect(),
+                });
+            }
+        }
+
+        // Constrói a atribuição de valores
+        let assignment = (0..self.n)
+            .map(|i| comp[2 * i] > comp[2 * i + 1])
+            .collect();
+        Solution::Satisfiable(assignment)
+    }
+
+    // Busca em largura no grafo de implicações; devolve os nós do caminho
+    // mais curto de `from` até `to`, incluindo os extremos
+    pub(crate) fn implication_path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut parent = vec![usize::MAX; self.graph.len()];
+        let mut queue = VecDeque::new();
+        parent[from] = from;
+        queue.push_back(from);
+
+        while let Some(u) = queue.pop_front() {
+            for &v in &self.graph[u] {
+                if parent[v] == usize::MAX {
+                    parent[v] = u;
+                    queue.push_back(v);
+                }
+            }
+            if parent[to] != usize::MAX {
+                break;
+            }
+        }
+
+        if parent[to] == usize::MAX {
+            return None;
+        }
+        let mut path = vec![to];
+        let mut u = to;
+        while u != from {
+            u = parent[u];
+            path.push(u);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Algoritmo de Kosaraju para encontrar SCCs
+    pub(crate) fn kosaraju(&self) -> (Vec<usize>, Vec<usize>) {
+        let n = self.graph.len();
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = Vec::new();
+
+        // Primeira DFS: ordenação por tempo de finalização
+        for i in 0..n {
+            if !visited[i] {
+                stack.push(i);
+                visited[i] = true;
+                
+                while let Some(&u) = stack.last() {
+                    let mut pushed = false;
+                    for &v in &self.graph[u] {
+                        if !visited[v] {
+                            visited[v] = true;
+                            stack.push(v);
+                            pushed = true;
+                            break;
+                        }
+                    }
+                    if !pushed {
+                        order.push(stack.pop().unwrap());
+                    }
+                }
+            }
+        }
+
+        // Segunda DFS: componente fortemente conexas
+        let mut comp = vec![0; n];
+        let mut comp_id = 0;
+        visited = vec![false; n];
+        
+        for &u in order.iter().rev() {
+            if !visited[u] {
+                let mut stack = VecDeque::new();
+                stack.push_back(u);
+                visited[u] = true;
+                comp[u] = comp_id;
+                
+                while let Some(u) = stack.pop_back() {
+                    for &v in &self.transposed[u] {
+                        if !visited[v] {
+                            visited[v] = true;
+                            comp[v] = comp_id;
+                            stack.push_back(v);
+                        }
+                    }
+                }
+                comp_id += 1;
+            }
+        }
+        (order, comp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_satisfiable() {
+        // Teste simples: (x0 ∨ x1) ∧ (¬x0 ∨ x1)
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(true, 0, true, 1);   // (x0 ∨ x1)
+        solver.add_clause(false, 0, true, 1);  // (¬x0 ∨ x1)
+        
+        let result = solver.solve();
+        assert!(result.is_some());
+        let assignment = result.unwrap();
+        
+        // Verifica se a atribuição satisfaz as cláusulas
+        let clause1 = assignment[0] || assignment[1];  // x0 ∨ x1
+        let clause2 = !assignment[0] || assignment[1]; // ¬x0 ∨ x1
+        
+        assert!(clause1);
+        assert!(clause2);
+    }
+
+    #[test]
+    fn test_unsatisfiable() {
+        // Teste insatisfatível: (x0 ∨ x1) ∧ (¬x0 ∨ ¬x1) ∧ (x0 ∨ ¬x1) ∧ (¬x0 ∨ x1)
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(true, 0, true, 1);    // (x0 ∨ x1)
+        solver.add_clause(false, 0, false, 1);  // (¬x0 ∨ ¬x1)
+        solver.add_clause(true, 0, false, 1);   // (x0 ∨ ¬x1)
+        solver.add_clause(false, 0, true, 1);   // (¬x0 ∨ x1)
+        
+        let result = solver.solve();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_single_variable() {
+        // Teste com uma única variável: (x0 ∨ x0) ∧ (¬x0 ∨ ¬x0)
+        let mut solver = TwoSATSolver::new(1);
+        solver.add_clause(true, 0, true, 0);    // (x0 ∨ x0) = x0
+        solver.add_clause(false, 0, false, 0);  // (¬x0 ∨ ¬x0) = ¬x0
+        
+        let result = solver.solve();
+        assert!(result.is_none()); // Contradição: x0 ∧ ¬x0
+    }
+
+    #[test]
+    // Confere `a || !a` de propósito, expressão que o clippy recusa por padrão
+    #[allow(clippy::overly_complex_bool_expr)]
+    fn test_tautology() {
+        // Teste com tautologia: (x0 ∨ ¬x0) - sempre satisfatível
+        let mut solver = TwoSATSolver::new(1);
+        solver.add_clause(true, 0, false, 0);   // (x0 ∨ ¬x0)
+        
+        let result = solver.solve();
+        assert!(result.is_some());
+        let assignment = result.unwrap();
+        
+        // Verifica se a atribuição satisfaz a cláusula
+        let clause = assignment[0] || !assignment[0];
+        assert!(clause);
+    }
+
+    #[test]
+    fn test_large_formula() {
+        // Teste com fórmula maior: 3 variáveis
+        let mut solver = TwoSATSolver::new(3);
+        
+        // (x0 ∨ x1) ∧ (¬x1 ∨ x2) ∧ (¬x0 ∨ x2) ∧ (x0 ∨ ¬x2)
+        solver.add_clause(true, 0, true, 1);    // (x0 ∨ x1)
+        solver.add_clause(false, 1, true, 2);   // (¬x1 ∨ x2)
+        solver.add_clause(false, 0, true, 2);   // (¬x0 ∨ x2)
+        solver.add_clause(true, 0, false, 2);   // (x0 ∨ ¬x2)
+        
+        let result = solver.solve();
+        assert!(result.is_some());
+        let assignment = result.unwrap();
+        
+        // Verifica se todas as cláusulas são satisfeitas
+        let clause1 = assignment[0] || assignment[1];
+        let clause2 = !assignment[1] || assignment[2];
+        let clause3 = !assignment[0] || assignment[2];
+        let clause4 = assignment[0] || !assignment[2];
+        
+        assert!(clause1);
+        assert!(clause2);
+        assert!(clause3);
+        assert!(clause4);
+    }
+
+    #[test]
+    fn test_empty_formula() {
+        // Teste com fórmula vazia - deve ser satisfatível
+        let solver = TwoSATSolver::new(2);
+        let result = solver.solve();
+        assert!(result.is_some());
+        let assignment = result.unwrap();
+        assert_eq!(assignment.len(), 2);
+    }
+
+    #[test]
+    fn test_contradiction_with_implication() {
+        // Teste que cria contradição através de implicações
+        let mut solver = TwoSATSolver::new(2);
+        
+        // x0 → x1, x1 → ¬x0, ¬x0 → x0
+        // Isso cria um ciclo que força x0 = ¬x0
+        solver.add_clause(false, 0, true, 1);   // (¬x0 ∨ x1) = x0 → x1
+        solver.add_clause(false, 1, false, 0);  // (¬x1 ∨ ¬x0) = x1 → ¬x0
+        solver.add_clause(true, 0, true, 0);    // (x0 ∨ x0) = x0
+        
+        let result = solver.solve();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_multiple_solutions() {
+        // Teste que pode ter múltiplas soluções
+        let mut solver = TwoSATSolver::new(2);
+        
+        // (x0 ∨ x1) - pode ser satisfeito de várias formas
+        solver.add_clause(true, 0, true, 1);
+        
+        let result = solver.solve();
+        assert!(result.is_some());
+        let assignment = result.unwrap();
+        
+        // Verifica se pelo menos uma das variáveis é verdadeira
+        assert!(assignment[0] || assignment[1]);
+    }
+
+    #[test]
+    fn test_chain_satisfiable() {
+        // Teste com 20 variáveis em uma cadeia de implicações: x0 → x1 → x2 → ... → x19
+        let n = 20;
+        let mut solver = TwoSATSolver::new(n);
+        for i in 0..n-1 {
+            solver.add_clause(false, i, true, i+1); // (¬xi ∨ xi+1)
+        }
+        // Adiciona uma cláusula que força x0 a ser verdadeiro
+        solver.add_clause(true, 0, true, 0); // (x0 ∨ x0)
+        let result = solver.solve();
+        assert!(result.is_some(), "A cadeia longa deveria ser satisfatível");
+        let assignment = result.unwrap();
+        // Todos devem ser verdadeiros
+        for (i, &val) in assignment.iter().enumerate() {
+            assert!(val, "x{} deveria ser verdadeiro", i);
+        }
+    }
+
+    #[test]
+    fn test_unsat_proof() {
+        // (x0 ∨ x0) ∧ (¬x0 ∨ x1) ∧ (¬x1 ∨ ¬x0): x0 → x1 → ¬x0 e ¬x0 → x0
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(true, 0, true, 0);
+        solver.add_clause(false, 0, true, 1);
+        solver.add_clause(false, 1, false, 0);
+
+        let proof = match solver.solve_with_proof() {
+            Solution::Unsatisfiable(proof) => proof,
+            Solution::Satisfiable(_) => panic!("A fórmula deveria ser insatisfatível"),
+        };
+        let x = Literal::new(proof.var, true);
+
+        assert_eq!(proof.to_negation.first(), Some(&x));
+        assert_eq!(proof.to_negation.last(), Some(&x.negate()));
+        assert_eq!(proof.from_negation.first(), Some(&x.negate()));
+        assert_eq!(proof.from_negation.last(), Some(&x));
+
+        // Cada passo dos caminhos tem que ser uma aresta do grafo de implicações
+        for path in [&proof.to_negation, &proof.from_negation] {
+            for step in path.windows(2) {
+                assert!(solver.graph[step[0].index()].contains(&step[1].index()));
+            }
+        }
+    }
+
+    // Gerador congruencial linear simples para os testes aleatórios
+    pub(crate) fn next(seed: &mut u64) -> usize {
+        *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (*seed >> 33) as usize
+    }
+
+    pub(crate) fn coin(seed: &mut u64) -> bool {
+        next(seed) & 1 == 0
+    }
+
+    pub(crate) fn random_clauses(seed: &mut u64, n: usize, max_len: usize) -> Vec<(bool, usize, bool, usize)> {
+        (0..next(seed) % max_len)
+            .map(|_| (coin(seed), next(seed) % n, coin(seed), next(seed) % n))
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_clause() {
+        // Sem a cláusula (x0 ∨ x0) a contradição desaparece
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(false, 0, true, 1);
+        solver.add_clause(false, 1, false, 0);
+        let unit = solver.add_clause(true, 0, true, 0);
+        assert!(solver.solve().is_none());
+
+        let removed = solver.remove_clause(unit);
+        assert_eq!(removed, Some(Clause { a: Literal::new(0, true), b: Literal::new(0, true) }));
+        assert_eq!(solver.clause(unit), None);
+        assert_eq!(solver.remove_clause(unit), None);
+        assert_eq!(solver.clauses().count(), 2);
+
+        let assignment = solver.solve().expect("Sem a cláusula unitária a fórmula é satisfatível");
+        assert!(!assignment[0]);
+    }
+
+    #[test]
+    fn test_random_add_remove_against_fresh_solver() {
+        let mut seed = 2024;
+        for _ in 0..100 {
+            let n = 1 + next(&mut seed) % 6;
+            let mut solver = TwoSATSolver::new(n);
+            let mut live: Vec<(ClauseId, (bool, usize, bool, usize))> = Vec::new();
+
+            for _ in 0..30 {
+                if !live.is_empty() && next(&mut seed).is_multiple_of(3) {
+                    let (id, _) = live.swap_remove(next(&mut seed) % live.len());
+                    assert!(solver.remove_clause(id).is_some());
+                } else {
+                    let clause = (coin(&mut seed), next(&mut seed) % n, coin(&mut seed), next(&mut seed) % n);
+                    let id = solver.add_clause(clause.0, clause.1, clause.2, clause.3);
+                    live.push((id, clause));
+                }
+
+                let mut fresh = TwoSATSolver::new(n);
+                for &(_, (a, a_var, b, b_var)) in &live {
+                    fresh.add_clause(a, a_var, b, b_var);
+                }
+                let result = solver.solve();
+                assert_eq!(result.is_some(), fresh.solve().is_some(), "Cláusulas: {:?}", live);
+                if let Some(assignment) = result {
+                    for &(_, (a, a_var, b, b_var)) in &live {
+                        assert!(assignment[a_var] == a || assignment[b_var] == b);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! 2-SAT quantificado (Q2SAT) em tempo linear, seguindo Aspvall, Plass e
+//! Tarjan (1979). Sobre as SCCs do grafo de implicações, a fórmula
+//! quantificada é falsa se, e somente se, ocorre uma das situações:
+//!
+//! 1. uma variável existencial está na mesma SCC que a sua negação;
+//! 2. um literal universal está na mesma SCC que um literal existencial
+//!    quantificado antes dele (a escolha existencial não pode depender dele);
+//! 3. existe um caminho de um literal universal até outro literal universal.
+
+use std::fmt;
+
+use crate::{Literal, TwoSATSolver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantifier {
+    Exists,
+    ForAll,
+}
+
+/// Motivo pelo qual a fórmula quantificada é falsa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Falsification {
+    /// A variável existencial `var` é equivalente à sua negação.
+    ExistentialContradiction { var: usize },
+    /// O literal universal `universal` determina o valor do literal
+    /// existencial `existential`, que é escolhido antes dele.
+    UniversalForcesEarlierExistential { universal: Literal, existential: Literal },
+    /// O literal universal `from` implica o literal universal `to`, então o
+    /// adversário escolhe `from` verdadeiro e `to` falso.
+    UniversalImpliesUniversal { from: Literal, to: Literal },
+}
+
+impl Falsification {
+    /// A variável universal que o adversário usa para falsificar a fórmula.
+    pub fn universal(&self) -> Option<usize> {
+        match *self {
+            Falsification::ExistentialContradiction { .. } => None,
+            Falsification::UniversalForcesEarlierExistential { universal, .. } => Some(universal.var),
+            Falsification::UniversalImpliesUniversal { from, .. } => Some(from.var),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantifiedResult {
+    True,
+    False(Falsification),
+}
+
+/// Prefixo que não quantifica cada variável do solver exatamente uma vez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixError {
+    VariableOutOfRange { var: usize, num_vars: usize },
+    QuantifiedTwice { var: usize },
+    Unquantified { var: usize },
+}
+
+impl fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefixError::VariableOutOfRange { var, num_vars } => {
+                write!(f, "variável x{var} fora do intervalo (o solver tem {num_vars} variáveis)")
+            }
+            PrefixError::QuantifiedTwice { var } => write!(f, "x{var} aparece duas vezes no prefixo"),
+            PrefixError::Unquantified { var } => write!(f, "x{var} não foi quantificada"),
+        }
+    }
+}
+
+impl std::error::Error for PrefixError {}
+
+impl TwoSATSolver {
+    // Resolve a fórmula quantificada ∘₁x₁ ∘₂x₂ … ∘ₙxₙ F, onde `prefix` lista
+    // cada variável exatamente uma vez, na ordem de quantificação
+    pub fn solve_quantified(&self, prefix: &[(Quantifier, usize)]) -> Result<QuantifiedResult, PrefixError> {
+        let mut quantifier = vec![None; self.n];
+        let mut position = vec![0; self.n];
+        for (pos, &(q, var)) in prefix.iter().enumerate() {
+            let slot = quantifier
+                .get_mut(var)
+                .ok_or(PrefixError::VariableOutOfRange { var, num_vars: self.n })?;
+            if slot.is_some() {
+                return Err(PrefixError::QuantifiedTwice { var });
+            }
+            *slot = Some(q);
+            position[var] = pos;
+        }
+        if let Some(var) = quantifier.iter().position(Option::is_none) {
+            return Err(PrefixError::Unquantified { var });
+        }
+        let is_universal = |node: usize| quantifier[node / 2] == Some(Quantifier::ForAll);
+
+        let (_, comp) = self.kosaraju();
+        let num_comps = comp.iter().map(|&c| c + 1).max().unwrap_or(0);
+
+        // Condição 1
+        for var in 0..self.n {
+            if quantifier[var] == Some(Quantifier::Exists) && comp[2 * var] == comp[2 * var + 1] {
+                return Ok(QuantifiedResult::False(Falsification::ExistentialContradiction { var }));
+            }
+        }
+
+        // Para cada SCC: um literal universal dela e o literal existencial
+        // quantificado mais cedo
+        let mut universal: Vec<Option<usize>> = vec![None; num_comps];
+        let mut earliest_existential: Vec<Option<usize>> = vec![None; num_comps];
+        for node in 0..self.graph.len() {
+            let c = comp[node];
+            if is_universal(node) {
+                // Condição 3 dentro da mesma SCC
+                if let Some(other) = universal[c] {
+                    return Ok(QuantifiedResult::False(Falsification::UniversalImpliesUniversal {
+                        from: Literal::from_index(other),
+                        to: Literal::from_index(node),
+                    }));
+                }
+                universal[c] = Some(node);
+            } else if earliest_existential[c].is_none_or(|e| position[node / 2] < position[e / 2]) {
+                earliest_existential[c] = Some(node);
+            }
+        }
+
+        // Condição 2
+        for c in 0..num_comps {
+            if let (Some(u), Some(e)) = (universal[c], earliest_existential[c])
+                && position[e / 2] < position[u / 2]
+            {
+                return Ok(QuantifiedResult::False(Falsification::UniversalForcesEarlierExistential {
+                    universal: Literal::from_index(u),
+                    existential: Literal::from_index(e),
+                }));
+            }
+        }
+
+        // Condição 3 entre SCCs diferentes. O Kosaraju numera as componentes
+        // em ordem topológica, então percorrendo-as de trás para frente todos
+        // os sucessores já foram processados. `reaches[c]` guarda um literal
+        // universal alcançável a partir de `c` por pelo menos uma aresta.
+        let mut nodes_by_comp = vec![Vec::new(); num_comps];
+        for node in 0..self.graph.len() {
+            nodes_by_comp[comp[node]].push(node);
+        }
+        let mut reaches: Vec<Option<usize>> = vec![None; num_comps];
+        for c in (0..num_comps).rev() {
+            for &u in &nodes_by_comp[c] {
+                for &v in &self.graph[u] {
+                    let d = comp[v];
+                    if d != c && reaches[c].is_none() {
+                        reaches[c] = universal[d].or(reaches[d]);
+                    }
+                }
+            }
+            if let (Some(from), Some(to)) = (universal[c], reaches[c]) {
+                return Ok(QuantifiedResult::False(Falsification::UniversalImpliesUniversal {
+                    from: Literal::from_index(from),
+                    to: Literal::from_index(to),
+                }));
+            }
+        }
+
+        Ok(QuantifiedResult::True)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{coin, next, random_clauses};
+    use Quantifier::{Exists, ForAll};
+
+    // Avalia a fórmula quantificada por força bruta
+    fn brute_force(clauses: &[(bool, usize, bool, usize)], prefix: &[(Quantifier, usize)], values: &mut Vec<bool>) -> bool {
+        let Some((&(q, var), rest)) = prefix.split_first() else {
+            return clauses.iter().all(|&(a, a_var, b, b_var)| values[a_var] == a || values[b_var] == b);
+        };
+        let mut results = [false, true].into_iter().map(|value| {
+            values[var] = value;
+            brute_force(clauses, rest, values)
+        });
+        match q {
+            Exists => results.any(|r| r),
+            ForAll => results.all(|r| r),
+        }
+    }
+
+    fn build(n: usize, clauses: &[(bool, usize, bool, usize)]) -> TwoSATSolver {
+        let mut solver = TwoSATSolver::new(n);
+        for &(a, a_var, b, b_var) in clauses {
+            solver.add_clause(a, a_var, b, b_var);
+        }
+        solver
+    }
+
+    #[test]
+    fn test_forall_exists() {
+        // ∀x0 ∃x1 (x0 ∨ x1) ∧ (¬x0 ∨ ¬x1): basta escolher x1 = ¬x0
+        let solver = build(2, &[(true, 0, true, 1), (false, 0, false, 1)]);
+        let result = solver.solve_quantified(&[(ForAll, 0), (Exists, 1)]).unwrap();
+        assert_eq!(result, QuantifiedResult::True);
+    }
+
+    #[test]
+    fn test_exists_forall() {
+        // ∃x1 ∀x0 (x0 ∨ x1) ∧ (¬x0 ∨ ¬x1): x1 teria que adivinhar x0
+        let solver = build(2, &[(true, 0, true, 1), (false, 0, false, 1)]);
+        let result = solver.solve_quantified(&[(Exists, 1), (ForAll, 0)]).unwrap();
+        match result {
+            QuantifiedResult::False(reason) => {
+                assert!(matches!(reason, Falsification::UniversalForcesEarlierExistential { .. }));
+                assert_eq!(reason.universal(), Some(0));
+            }
+            QuantifiedResult::True => panic!("A fórmula deveria ser falsa"),
+        }
+    }
+
+    #[test]
+    fn test_universal_implies_universal() {
+        // ∀x0 ∀x1 (x0 ∨ x1): o adversário escolhe x0 = x1 = falso
+        let solver = build(2, &[(true, 0, true, 1)]);
+        let result = solver.solve_quantified(&[(ForAll, 0), (ForAll, 1)]).unwrap();
+        match result {
+            QuantifiedResult::False(Falsification::UniversalImpliesUniversal { from, to }) => {
+                assert!(!from.value && to.value);
+                assert_ne!(from.var, to.var);
+            }
+            other => panic!("Resultado inesperado: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_existential_contradiction() {
+        let solver = build(1, &[(true, 0, true, 0), (false, 0, false, 0)]);
+        let result = solver.solve_quantified(&[(Exists, 0)]).unwrap();
+        assert_eq!(
+            result,
+            QuantifiedResult::False(Falsification::ExistentialContradiction { var: 0 })
+        );
+    }
+
+    #[test]
+    fn test_malformed_prefix() {
+        let solver = build(2, &[(true, 0, true, 1)]);
+        assert_eq!(
+            solver.solve_quantified(&[(Exists, 0), (ForAll, 2)]),
+            Err(PrefixError::VariableOutOfRange { var: 2, num_vars: 2 })
+        );
+        assert_eq!(
+            solver.solve_quantified(&[(Exists, 1), (ForAll, 1)]),
+            Err(PrefixError::QuantifiedTwice { var: 1 })
+        );
+        assert_eq!(solver.solve_quantified(&[(ForAll, 1)]), Err(PrefixError::Unquantified { var: 0 }));
+        assert_eq!(solver.solve_quantified(&[]), Err(PrefixError::Unquantified { var: 0 }));
+    }
+
+    #[test]
+    fn test_all_existential_matches_solve() {
+        let mut seed = 7;
+        for _ in 0..200 {
+            let n = 1 + next(&mut seed) % 6;
+            let clauses = random_clauses(&mut seed, n, 10);
+            let solver = build(n, &clauses);
+            let prefix: Vec<_> = (0..n).map(|var| (Exists, var)).collect();
+
+            let quantified = solver.solve_quantified(&prefix) == Ok(QuantifiedResult::True);
+            assert_eq!(quantified, solver.solve().is_some(), "Cláusulas: {:?}", clauses);
+        }
+    }
+
+    #[test]
+    fn test_random_against_brute_force() {
+        let mut seed = 42;
+        for _ in 0..500 {
+            let n = 1 + next(&mut seed) % 5;
+            let clauses = random_clauses(&mut seed, n, 8);
+
+            // Prefixo com quantificadores e ordem aleatórios
+            let mut order: Vec<usize> = (0..n).collect();
+            for i in (1..n).rev() {
+                order.swap(i, next(&mut seed) % (i + 1));
+            }
+            let prefix: Vec<_> = order.into_iter()
+                .map(|var| (if coin(&mut seed) { Exists } else { ForAll }, var))
+                .collect();
+
+            let solver = build(n, &clauses);
+            let expected = brute_force(&clauses, &prefix, &mut vec![false; n]);
+            let result = solver.solve_quantified(&prefix).unwrap();
+            assert_eq!(
+                result == QuantifiedResult::True, expected,
+                "Prefixo: {:?}, cláusulas: {:?}, resultado: {:?}", prefix, clauses, result
+            );
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Serialização do solver e das soluções em JSON e num formato binário
+//! compacto (bincode). Desserializar um solver e resolvê-lo de novo produz
+//! exatamente o mesmo resultado, pois as cláusulas são reinseridas na ordem
+//! original e o grafo de implicações é reconstruído igual.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{Clause, TwoSATSolver};
+
+// Representação serializada do solver: o grafo é derivado das cláusulas,
+// então só guardamos o que é necessário para reconstruí-lo
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SolverState {
+    num_vars: usize,
+    names: Vec<Option<String>>,
+    // Cláusulas removidas ficam como `null` para preservar os `ClauseId`s
+    clauses: Vec<Option<Clause>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    VariableOutOfRange { var: usize, num_vars: usize },
+    NameCountMismatch { names: usize, num_vars: usize },
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::VariableOutOfRange { var, num_vars } => {
+                write!(f, "variável x{var} fora do intervalo (o solver tem {num_vars} variáveis)")
+            }
+            StateError::NameCountMismatch { names, num_vars } => {
+                write!(f, "{names} nomes para {num_vars} variáveis")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl From<TwoSATSolver> for SolverState {
+    fn from(solver: TwoSATSolver) -> Self {
+        SolverState {
+            num_vars: solver.n,
+            names: solver.names,
+            clauses: solver.clauses,
+        }
+    }
+}
+
+impl TryFrom<SolverState> for TwoSATSolver {
+    type Error = StateError;
+
+    fn try_from(state: SolverState) -> Result<Self, Self::Error> {
+        if state.names.len() != state.num_vars {
+            return Err(StateError::NameCountMismatch {
+                names: state.names.len(),
+                num_vars: state.num_vars,
+            });
+        }
+
+        let mut solver = TwoSATSolver::new(state.num_vars);
+        for clause in state.clauses {
+            let Some(clause) = clause else {
+                solver.clauses.push(None);
+                continue;
+            };
+            for literal in [clause.a, clause.b] {
+                if literal.var >= state.num_vars {
+                    return Err(StateError::VariableOutOfRange {
+                        var: literal.var,
+                        num_vars: state.num_vars,
+                    });
+                }
+            }
+            solver.add_clause(clause.a.value, clause.a.var, clause.b.value, clause.b.var);
+        }
+        solver.names = state.names;
+        Ok(solver)
+    }
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(value)
+}
+
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+pub fn to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>, bincode::Error> {
+    bincode::serialize(value)
+}
+
+pub fn from_bytes<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, bincode::Error> {
+    bincode::deserialize(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use crate::tests::{next, random_clauses};
+
+    fn unsatisfiable_solver() -> TwoSATSolver {
+        // (x0 ∨ x1) ∧ (¬x0 ∨ ¬x1) ∧ (x0 ∨ ¬x1) ∧ (¬x0 ∨ x1)
+        let mut solver = TwoSATSolver::new(2);
+        solver.add_clause(true, 0, true, 1);
+        solver.add_clause(false, 0, false, 1);
+        solver.add_clause(true, 0, false, 1);
+        solver.add_clause(false, 0, true, 1);
+        solver
+    }
+
+    fn satisfiable_solver() -> TwoSATSolver {
+        // (x0 ∨ x1) ∧ (¬x1 ∨ x2) ∧ (¬x0 ∨ x2) ∧ (x0 ∨ ¬x2)
+        let mut solver = TwoSATSolver::new(3);
+        solver.add_clause(true, 0, true, 1);
+        solver.add_clause(false, 1, true, 2);
+        solver.add_clause(false, 0, true, 2);
+        solver.add_clause(true, 0, false, 2);
+        let removed = solver.add_clause(false, 0, false, 2);
+        solver.remove_clause(removed);
+        solver.set_var_name(0, "chuva");
+        solver.set_var_name(2, "guarda-chuva");
+        solver
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        for solver in [satisfiable_solver(), unsatisfiable_solver()] {
+            let json = to_json(&solver).unwrap();
+            let restored: TwoSATSolver = from_json(&json).unwrap();
+
+            assert_eq!(restored.num_vars(), solver.num_vars());
+            assert!(restored.clauses().eq(solver.clauses()));
+            for var in 0..solver.num_vars() {
+                assert_eq!(restored.var_name(var), solver.var_name(var));
+            }
+            assert_eq!(restored.solve_with_proof(), solver.solve_with_proof());
+        }
+    }
+
+    #[test]
+    fn test_round_trip_after_removing_middle_clauses() {
+        // Um solver restaurado monta o grafo com as cláusulas em ordem de
+        // inserção; remover uma cláusula do meio não pode mudar a ordem das
+        // arestas restantes, senão a atribuição e a prova podem mudar
+        let mut seed = 2024;
+        for _ in 0..400 {
+            let n = 1 + next(&mut seed) % 5;
+            let clauses = random_clauses(&mut seed, n, 16);
+            if clauses.len() < 6 {
+                continue;
+            }
+            let mut solver = TwoSATSolver::new(n);
+            let mut ids: Vec<_> = clauses
+                .iter()
+                .map(|&(a, a_var, b, b_var)| solver.add_clause(a, a_var, b, b_var))
+                .collect();
+            // Remove cláusulas de qualquer posição, nunca só a última
+            for _ in 0..4 {
+                let id = ids.remove(next(&mut seed) % (ids.len() - 1));
+                solver.remove_clause(id);
+            }
+
+            let restored: TwoSATSolver = from_json(&to_json(&solver).unwrap()).unwrap();
+            assert_eq!(restored.solve(), solver.solve());
+            assert_eq!(restored.solve_with_proof(), solver.solve_with_proof());
+        }
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        for solver in [satisfiable_solver(), unsatisfiable_solver()] {
+            let bytes = to_bytes(&solver).unwrap();
+            let restored: TwoSATSolver = from_bytes(&bytes).unwrap();
+
+            assert!(restored.clauses().eq(solver.clauses()));
+            assert_eq!(restored.solve_with_proof(), solver.solve_with_proof());
+        }
+    }
+
+    #[test]
+    fn test_solution_round_trip() {
+        for solver in [satisfiable_solver(), unsatisfiable_solver()] {
+            let solution = solver.solve_with_proof();
+
+            let from_text: Solution = from_json(&to_json(&solution).unwrap()).unwrap();
+            let from_binary: Solution = from_bytes(&to_bytes(&solution).unwrap()).unwrap();
+
+            assert_eq!(from_text, solution);
+            assert_eq!(from_binary, solution);
+        }
+    }
+
+    #[test]
+    fn test_rejects_variable_out_of_range() {
+        let json = r#"{"num_vars":1,"names":[null],"clauses":[{"a":{"var":0,"value":true},"b":{"var":3,"value":false}}]}"#;
+        let result: Result<TwoSATSolver, _> = from_json(json);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rejects_name_count_mismatch() {
+        let json = r#"{"num_vars":2,"names":[null],"clauses":[]}"#;
+        let result: Result<TwoSATSolver, _> = from_json(json);
+        assert!(result.is_err());
+    }
+}